@@ -0,0 +1,51 @@
+use chrono::{Duration, NaiveTime};
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use gw2timers::meta::MapMetaKind;
+
+fn times() -> Vec<NaiveTime> {
+    (0..24 * 60)
+        .step_by(37)
+        .map(|minutes| NaiveTime::MIN + Duration::minutes(minutes))
+        .collect()
+}
+
+fn bench_next(c: &mut Criterion) {
+    let times = times();
+    c.bench_function("next 100 events of every meta", |b| {
+        b.iter(|| {
+            for kind in MapMetaKind::all_keys() {
+                let mut iter = kind.into_iter().time(black_box(times[0]));
+                for _ in 0..100 {
+                    black_box(iter.next());
+                }
+            }
+        })
+    });
+    c.bench_function("first event of every meta", |b| {
+        b.iter(|| {
+            for kind in MapMetaKind::all_keys() {
+                for time in &times {
+                    black_box(kind.into_iter().time(black_box(*time)).next());
+                }
+            }
+        })
+    });
+}
+
+fn bench_now(c: &mut Criterion) {
+    let times = times();
+    c.bench_function("active event of every meta", |b| {
+        b.iter(|| {
+            for kind in MapMetaKind::all_keys() {
+                for time in &times {
+                    black_box(kind.into_iter().time(black_box(*time)).now());
+                }
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_next, bench_now);
+criterion_main!(benches);
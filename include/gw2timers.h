@@ -0,0 +1,123 @@
+/*
+ * Generated with cbindgen from src/ffi.rs. Do not edit by hand.
+ *
+ * Build the library with `cargo rustc --lib --release --features ffi --crate-type cdylib`.
+ *
+ * Map metas are referred to by their index, from 0 up to gw2timers_meta_count(). Times are Unix
+ * timestamps in seconds.
+ *
+ * Strings returned by this library live until the program exits and must not be freed. A
+ * Gw2MetaIter must be freed with gw2timers_meta_iter_free() exactly once. `out` structs are only
+ * written to when a function returns true.
+ */
+
+#ifndef GW2TIMERS_H
+#define GW2TIMERS_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// An iterator over the events in a map meta
+typedef struct Gw2MetaIter Gw2MetaIter;
+
+// How often an event happens
+typedef struct Gw2Schedule {
+  // The name of the event
+  const char *name;
+  // The minutes after UTC 00:00 the event first starts
+  int64_t offset_minutes;
+  // The minutes between each start of the event
+  int64_t frequency_minutes;
+  // The minutes the event lasts
+  int64_t length_minutes;
+} Gw2Schedule;
+
+// A single occurrence of an event
+typedef struct Gw2Event {
+  // The name of the event
+  const char *name;
+  // The name of the map meta the event is in
+  const char *meta;
+  // When the event starts
+  int64_t start;
+  // When the event ends
+  int64_t end;
+} Gw2Event;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// The number of map metas
+size_t gw2timers_meta_count(void);
+
+// The name of a map meta, or null if `meta` isn't a map meta
+const char *gw2timers_meta_name(size_t meta);
+
+// The name of a map meta's category, or null if `meta` isn't a map meta
+const char *gw2timers_meta_category(size_t meta);
+
+// The number of event schedules in a map meta, or 0 if `meta` isn't a map meta
+size_t gw2timers_schedule_count(size_t meta);
+
+// Write one of a map meta's event schedules to `out`
+//
+// Returns false if `meta` or `schedule` is out of range.
+//
+// # Safety
+//
+// `out` must be null or point to a writable [Gw2Schedule].
+bool gw2timers_schedule(size_t meta, size_t schedule, struct Gw2Schedule *out);
+
+// Write the event active in a map meta at `now` to `out`
+//
+// Returns false if there is no active event, `meta` isn't a map meta, or the event's times are
+// past the range of dates this library handles.
+//
+// # Safety
+//
+// `out` must be null or point to a writable [Gw2Event].
+bool gw2timers_active(size_t meta, int64_t now, struct Gw2Event *out);
+
+// Write the next event to start in a map meta after `now` to `out`
+//
+// Returns false if `meta` isn't a map meta, or the event's times are past the range of dates this
+// library handles.
+//
+// # Safety
+//
+// `out` must be null or point to a writable [Gw2Event].
+bool gw2timers_next(size_t meta, int64_t now, struct Gw2Event *out);
+
+// Create an iterator over the events in a map meta that start after `now`
+//
+// Returns null if `meta` isn't a map meta. The iterator must be freed with
+// [gw2timers_meta_iter_free].
+struct Gw2MetaIter *gw2timers_meta_iter_new(size_t meta, int64_t now);
+
+// Write the iterator's next event to `out`
+//
+// Returns false if `iter` is null, or the event's times are past the range of dates this library
+// handles.
+//
+// # Safety
+//
+// `iter` must be null or an iterator from [gw2timers_meta_iter_new] that hasn't been freed, and
+// `out` must be null or point to a writable [Gw2Event].
+bool gw2timers_meta_iter_next(struct Gw2MetaIter *iter, struct Gw2Event *out);
+
+// Free an iterator
+//
+// # Safety
+//
+// `iter` must be null or an iterator from [gw2timers_meta_iter_new] that hasn't been freed.
+void gw2timers_meta_iter_free(struct Gw2MetaIter *iter);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* GW2TIMERS_H */
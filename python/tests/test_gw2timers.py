@@ -0,0 +1,82 @@
+import itertools
+import unittest
+from datetime import datetime, time, timedelta, timezone
+
+from gw2timers import EventInstance, EventSchedule, MapMetaKind
+
+NOW = datetime(2022, 3, 1, 8, 41, tzinfo=timezone.utc)
+
+
+class TestMapMetaKind(unittest.TestCase):
+    def test_all(self):
+        metas = MapMetaKind.all()
+        self.assertEqual(len(metas), 28)
+        self.assertEqual(metas[1], MapMetaKind.WorldBosses)
+        self.assertEqual(len(set(metas)), 28)
+
+    def test_info(self):
+        self.assertEqual(MapMetaKind.WorldBosses.name, "World Bosses")
+        self.assertEqual(MapMetaKind.AuricBasin.category, "Heart of Thorns")
+
+    def test_from_name(self):
+        self.assertEqual(MapMetaKind.from_name("tangled depths"), MapMetaKind.TangledDepths)
+        with self.assertRaises(ValueError):
+            MapMetaKind.from_name("nowhere")
+
+    def test_iter_from(self):
+        events = list(itertools.islice(MapMetaKind.WorldBosses.iter_from(NOW), 3))
+        self.assertEqual(
+            [event.name for event in events],
+            ["Fire Elemental", "Admiral Taidha Covington", "Great Jungle Wurm"],
+        )
+        self.assertIsInstance(events[0], EventInstance)
+        self.assertEqual(events[0].start, datetime(2022, 3, 1, 8, 45, tzinfo=timezone.utc))
+        self.assertEqual(events[0].end - events[0].start, timedelta(minutes=15))
+
+    def test_iter_from_other_timezone(self):
+        berlin = timezone(timedelta(hours=1))
+        event = next(MapMetaKind.WorldBosses.iter_from(NOW.astimezone(berlin)))
+        self.assertEqual(event.name, "Fire Elemental")
+        self.assertEqual(event.start.tzinfo, timezone.utc)
+
+    def test_iter_from_now(self):
+        event = next(MapMetaKind.WorldBosses.iter_from())
+        self.assertGreater(event.start, datetime.now(timezone.utc))
+
+    def test_active(self):
+        self.assertEqual(MapMetaKind.WorldBosses.active(NOW).name, "Claw of Jormag")
+        self.assertIsNone(MapMetaKind.LeyLineAnomaly.active(NOW))
+
+
+class TestEventSchedule(unittest.TestCase):
+    def test_fields(self):
+        dusk = MapMetaKind.DayAndNight.schedules[2]
+        self.assertIsInstance(dusk, EventSchedule)
+        self.assertEqual(dusk.name, "Dusk")
+        self.assertEqual(dusk.offset, time(1, 40))
+        self.assertEqual(dusk.frequency, timedelta(hours=2))
+        self.assertEqual(dusk.length, timedelta(minutes=5))
+        self.assertEqual(
+            repr(dusk), "EventSchedule(name='Dusk', offset=01:40, frequency=120m, length=5m)"
+        )
+
+    def test_iter_from(self):
+        dusk = MapMetaKind.DayAndNight.schedules[2]
+        starts = [event.start for event in itertools.islice(dusk.iter_from(NOW), 2)]
+        self.assertEqual(
+            starts,
+            [
+                datetime(2022, 3, 1, 9, 40, tzinfo=timezone.utc),
+                datetime(2022, 3, 1, 11, 40, tzinfo=timezone.utc),
+            ],
+        )
+
+    def test_active(self):
+        day = MapMetaKind.DayAndNight.schedules[1]
+        event = day.active(NOW)
+        self.assertEqual(event.schedule.name, "Day")
+        self.assertEqual(event.end, datetime(2022, 3, 1, 9, 40, tzinfo=timezone.utc))
+
+
+if __name__ == "__main__":
+    unittest.main()
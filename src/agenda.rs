@@ -0,0 +1,111 @@
+//! Builds the timetable of map meta events for a calendar day
+//!
+//! An [Agenda] lists every event active at some point during a day in a time zone, including
+//! those that started the day before and are still running at midnight.
+
+use alloc::{vec, vec::Vec};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+
+use crate::{event::EventInstance, meta::MapMetaKind};
+
+/// An occurrence of an event in an [Agenda]
+#[derive(Clone, Debug)]
+pub struct Entry<Tz: TimeZone> {
+    pub meta: MapMetaKind,
+
+    /// The event, with times from UTC 00:00 of [Agenda::base_date]
+    pub event: EventInstance,
+
+    pub start: DateTime<Tz>,
+    pub end: DateTime<Tz>,
+
+    /// Whether the event started before the day did
+    pub carried_over: bool,
+}
+
+/// Every map meta event active during a calendar day
+#[derive(Clone, Debug)]
+pub struct Agenda<Tz: TimeZone> {
+    /// The day in the agenda's time zone
+    pub date: NaiveDate,
+
+    /// The UTC day the times of each [EventInstance] are from
+    pub base_date: NaiveDate,
+
+    /// Every event active during the day, in order of when they start
+    pub entries: Vec<Entry<Tz>>,
+}
+
+impl<Tz: TimeZone> Agenda<Tz> {
+    /// The agenda of every map meta on `date` in `tz`
+    pub fn new(date: NaiveDate, tz: &Tz) -> Self {
+        Agenda::for_metas(date, tz, &MapMetaKind::all_keys())
+    }
+
+    /// The agenda of only `metas` on `date` in `tz`
+    ///
+    /// The day runs from the first moment of `date` to the first moment of the next day, so it
+    /// may be shorter or longer than 24 hours when daylight saving time starts or ends.
+    pub fn for_metas(date: NaiveDate, tz: &Tz, metas: &[MapMetaKind]) -> Self {
+        let day_start = start_of_day(tz, date);
+        let day_end = start_of_day(tz, date.succ_opt().unwrap_or(date));
+        let base_date = day_start.date_naive();
+        let base = base_date.and_time(NaiveTime::MIN).and_utc();
+        let (start, end) = (day_start - base, day_end - base);
+
+        let mut entries = Vec::new();
+        for &meta in metas {
+            let table = meta.cycle_table();
+            let mut events = meta.into_iter();
+            // Events that started this long before midnight may still be running
+            events.seek(start - table.longest() - Duration::minutes(1));
+            for event in events.take_while(|event| event.start_time < end) {
+                if event.end_time() <= start && event.start_time < start {
+                    continue;
+                }
+                entries.push(Entry {
+                    meta,
+                    start: event.start_datetime(base_date).with_timezone(tz),
+                    end: event.end_datetime(base_date).with_timezone(tz),
+                    carried_over: event.start_time < start,
+                    event,
+                });
+            }
+        }
+        entries.sort_by_key(|entry| (entry.event.start_time, entry.meta as usize));
+
+        Agenda {
+            date,
+            base_date,
+            entries,
+        }
+    }
+
+    /// The entries of each map meta with any events during the day, in the order of
+    /// [MapMetaKind::all_keys]
+    pub fn by_meta(&self) -> Vec<(MapMetaKind, Vec<&Entry<Tz>>)> {
+        let mut groups: Vec<(MapMetaKind, Vec<&Entry<Tz>>)> = Vec::new();
+        for entry in &self.entries {
+            match groups.iter_mut().find(|(meta, _)| *meta == entry.meta) {
+                Some((_, entries)) => entries.push(entry),
+                None => groups.push((entry.meta, vec![entry])),
+            }
+        }
+        groups.sort_by_key(|(meta, _)| *meta as usize);
+        groups
+    }
+}
+
+/// The first moment of `date` in `tz`, which is after midnight when midnight is skipped by a
+/// daylight saving time change
+fn start_of_day<Tz: TimeZone>(tz: &Tz, date: NaiveDate) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    (0..24 * 60)
+        .find_map(|minute| {
+            tz.from_local_datetime(&(midnight + Duration::minutes(minute)))
+                .earliest()
+        })
+        .map(|time| time.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
@@ -0,0 +1,233 @@
+//! Summarises how often and how long map meta events are active
+//!
+//! [Analytics] gives, for a set of map metas, how many times each event starts in a day or week
+//! and the share of that time it is active, along with a [Heatmap] of how many events are active
+//! at once in each hour, e.g. to pick the quietest hours for a raid night.
+
+use alloc::{vec, vec::Vec};
+
+use chrono::Duration;
+
+use crate::{event::EventInstance, meta::MapMetaKind};
+
+/// The length of time analytics are taken over, starting from UTC 00:00
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Span {
+    Day,
+    Week,
+}
+
+impl Span {
+    /// How long the span lasts
+    pub fn duration(self) -> Duration {
+        match self {
+            Span::Day => Duration::days(1),
+            Span::Week => Duration::weeks(1),
+        }
+    }
+
+    /// How many hours the span lasts
+    pub fn hours(self) -> usize {
+        match self {
+            Span::Day => 24,
+            Span::Week => 7 * 24,
+        }
+    }
+}
+
+/// How often an event of a map meta occurs over a [Span]
+#[derive(Clone, Debug, PartialEq)]
+pub struct EventStats {
+    pub meta: MapMetaKind,
+    pub name: &'static str,
+
+    /// How many times the event starts during the span
+    pub occurrences: u32,
+
+    /// How long the event is active during the span, counting occurrences running over either
+    /// end of it up to the edge
+    pub active: Duration,
+
+    /// The share of the span the event is active, from 0 to 1
+    pub duty_cycle: f64,
+}
+
+/// How busy an hour of a [Heatmap] is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hour {
+    /// The most events active at once during the hour
+    pub peak: u32,
+
+    /// The total time events are active during the hour, counting each event separately
+    pub active: Duration,
+}
+
+impl Hour {
+    /// How many events are active at once during the hour on average
+    pub fn average(&self) -> f64 {
+        self.active.num_seconds() as f64 / 3600.0
+    }
+}
+
+/// How many events are active at once in each hour of a [Span]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heatmap {
+    pub span: Span,
+
+    /// Each hour of the span from UTC 00:00
+    pub hours: Vec<Hour>,
+}
+
+impl Heatmap {
+    /// The index of the hour with the most activity, the earliest if there is a tie
+    pub fn busiest(&self) -> Option<usize> {
+        (0..self.hours.len())
+            .rev()
+            .max_by_key(|&hour| (self.hours[hour].active, self.hours[hour].peak))
+    }
+
+    /// The index of the hour with the least activity, the earliest if there is a tie
+    pub fn quietest(&self) -> Option<usize> {
+        (0..self.hours.len()).min_by_key(|&hour| (self.hours[hour].active, self.hours[hour].peak))
+    }
+}
+
+/// Summarises the events of a set of map metas
+///
+/// Hours of a [Span::Week] are counted from UTC 00:00 of any day, as every built in map meta
+/// repeats daily.
+pub struct Analytics {
+    pub metas: Vec<MapMetaKind>,
+}
+
+impl Analytics {
+    pub fn new(metas: &[MapMetaKind]) -> Self {
+        Analytics {
+            metas: metas.to_vec(),
+        }
+    }
+
+    /// Summarise every map meta
+    pub fn all() -> Self {
+        Analytics::new(&MapMetaKind::all_keys())
+    }
+
+    /// How often each event occurs over `span`, grouped by map meta, with events of the same name
+    /// counted together
+    pub fn events(&self, span: Span) -> Vec<EventStats> {
+        let length = span.duration();
+        let mut stats = Vec::new();
+        for &meta in &self.metas {
+            let first = stats.len();
+            // The times each event is active, to merge any occurrences that overlap
+            let mut active: Vec<Vec<(Duration, Duration)>> = Vec::new();
+            for event in occurrences(meta, length) {
+                let name = event.schedule.name;
+                let index = match stats[first..]
+                    .iter()
+                    .position(|stats: &EventStats| stats.name == name)
+                {
+                    Some(index) => index,
+                    None => {
+                        stats.push(EventStats {
+                            meta,
+                            name,
+                            occurrences: 0,
+                            active: Duration::zero(),
+                            duty_cycle: 0.0,
+                        });
+                        active.push(Vec::new());
+                        active.len() - 1
+                    }
+                };
+                if event.start_time >= Duration::zero() {
+                    stats[first + index].occurrences += 1;
+                }
+                let (start, end) = clip(&event, length);
+                if start < end {
+                    active[index].push((start, end));
+                }
+            }
+
+            for (stats, mut intervals) in stats[first..].iter_mut().zip(active) {
+                intervals.sort_by_key(|&(start, _)| start);
+                let mut free_from = Duration::zero();
+                for (start, end) in intervals {
+                    let start = start.max(free_from);
+                    if start < end {
+                        stats.active += end - start;
+                        free_from = end;
+                    }
+                }
+                stats.duty_cycle = stats.active.num_seconds() as f64 / length.num_seconds() as f64;
+            }
+        }
+        stats
+    }
+
+    /// How many events are active at once in each hour of `span`
+    pub fn heatmap(&self, span: Span) -> Heatmap {
+        let length = span.duration();
+        // Every time an event starts or ends, with ends sorted before starts at the same time
+        let mut edges = Vec::new();
+        for &meta in &self.metas {
+            for event in occurrences(meta, length) {
+                let (start, end) = clip(&event, length);
+                if start < end {
+                    edges.push((start, 1));
+                    edges.push((end, -1));
+                }
+            }
+        }
+        edges.sort();
+
+        let mut hours = vec![
+            Hour {
+                peak: 0,
+                active: Duration::zero(),
+            };
+            span.hours()
+        ];
+        let mut active: i64 = 0;
+        let mut from = Duration::zero();
+        for (time, change) in edges {
+            if active > 0 && from < time {
+                add_activity(&mut hours, from, time, active);
+            }
+            active += change;
+            from = time;
+        }
+
+        Heatmap { span, hours }
+    }
+}
+
+/// Every occurrence of a map meta's events active at some point from UTC 00:00 until `end`
+fn occurrences(meta: MapMetaKind, end: Duration) -> impl Iterator<Item = EventInstance> {
+    let mut events = meta.into_iter();
+    // Events that started this long before UTC 00:00 may still be active
+    events.seek(-meta.cycle_table().longest() - Duration::minutes(1));
+    events
+        .take_while(move |event| event.start_time < end)
+        .filter(|event| event.end_time() > Duration::zero() || event.start_time >= Duration::zero())
+}
+
+/// The part of an event's occurrence from UTC 00:00 until `end`
+fn clip(event: &EventInstance, end: Duration) -> (Duration, Duration) {
+    (
+        event.start_time.max(Duration::zero()),
+        event.end_time().min(end),
+    )
+}
+
+/// Count `count` events as active from `start` until `end` in each hour they cover
+fn add_activity(hours: &mut [Hour], start: Duration, end: Duration, count: i64) {
+    let first = start.num_hours() as usize;
+    let last = ((end - Duration::seconds(1)).num_hours() as usize).min(hours.len() - 1);
+    for (index, hour) in hours.iter_mut().enumerate().take(last + 1).skip(first) {
+        let hour_start = Duration::hours(index as i64);
+        let overlap = end.min(hour_start + Duration::hours(1)) - start.max(hour_start);
+        hour.peak = hour.peak.max(count as u32);
+        hour.active += overlap * count as i32;
+    }
+}
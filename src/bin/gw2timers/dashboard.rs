@@ -0,0 +1,333 @@
+//! A live terminal dashboard of every map meta
+//!
+//! [App] holds everything shown and [render] draws it to any ratatui backend, so the dashboard can
+//! be drawn without a real terminal.
+
+use std::{collections::HashSet, io, time::Duration as StdDuration};
+
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use gw2timers::{
+    category::Category,
+    clock::{Clock, FixedClock},
+    event::EventInstance,
+    meta::MapMetaKind,
+};
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Cell, Row, Table, TableState},
+    Frame,
+};
+
+/// The state of a single map meta at the current time
+struct Status {
+    kind: MapMetaKind,
+    active: Option<EventInstance>,
+    next: Option<EventInstance>,
+}
+
+/// Everything the dashboard shows
+pub struct App {
+    /// The time being shown
+    now: DateTime<Utc>,
+
+    /// The time zone to show times in
+    tz: Tz,
+
+    statuses: Vec<Status>,
+
+    /// The index of the selected meta in the visible list
+    selected: usize,
+
+    /// Only show metas in this category
+    category: Option<Category>,
+
+    favourites: HashSet<MapMetaKind>,
+
+    /// Only show favourite metas
+    favourites_only: bool,
+}
+
+impl App {
+    pub fn new(tz: Tz, favourites: &[MapMetaKind]) -> Self {
+        App {
+            now: DateTime::<Utc>::MIN_UTC,
+            tz,
+            statuses: Vec::new(),
+            selected: 0,
+            category: None,
+            favourites: favourites.iter().copied().collect(),
+            favourites_only: false,
+        }
+    }
+
+    /// Recalculate every meta's events at `now`
+    pub fn update(&mut self, now: DateTime<Utc>) {
+        let clock = FixedClock::new(now);
+        self.now = now;
+        self.statuses = MapMetaKind::all_keys()
+            .iter()
+            .map(|kind| {
+                let mut iter = kind.iter_from(&clock);
+                Status {
+                    kind: *kind,
+                    active: iter.now(),
+                    next: iter.next(),
+                }
+            })
+            .collect();
+    }
+
+    /// Handle a key press, returning whether the dashboard should quit
+    pub fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Down | KeyCode::Char('j') if self.selected + 1 < self.visible().len() => {
+                self.selected += 1;
+            }
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('f') => {
+                if let Some(status) = self.visible().get(self.selected) {
+                    let kind = status.kind;
+                    if !self.favourites.remove(&kind) {
+                        self.favourites.insert(kind);
+                    }
+                }
+                self.clamp_selected();
+            }
+            KeyCode::Char('F') => {
+                self.favourites_only = !self.favourites_only;
+                self.clamp_selected();
+            }
+            KeyCode::Char('c') => {
+                // Cycle through every category, then back to showing all of them
+                let categories = Category::all();
+                self.category = match self.category {
+                    None => categories.first().copied(),
+                    Some(category) => categories
+                        .iter()
+                        .skip_while(|c| **c != category)
+                        .nth(1)
+                        .copied(),
+                };
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// The metas that pass the current filters
+    fn visible(&self) -> Vec<&Status> {
+        self.statuses
+            .iter()
+            .filter(|status| match self.category {
+                Some(category) => status.kind.info().category == category,
+                None => true,
+            })
+            .filter(|status| !self.favourites_only || self.favourites.contains(&status.kind))
+            .collect()
+    }
+
+    fn clamp_selected(&mut self) {
+        self.selected = self.selected.min(self.visible().len().saturating_sub(1));
+    }
+}
+
+/// Draw the dashboard
+pub fn render(frame: &mut Frame, app: &App) {
+    let [header, body, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    let filter = match app.category {
+        Some(category) => category.name(),
+        None => "All categories",
+    };
+    let favourites = if app.favourites_only {
+        ", favourites only"
+    } else {
+        ""
+    };
+    frame.render_widget(
+        Line::from(format!(
+            "{}  ({}{})",
+            app.now
+                .with_timezone(&app.tz)
+                .format("%Y-%m-%d %H:%M:%S %Z"),
+            filter,
+            favourites
+        )),
+        header,
+    );
+
+    let date = app.now.date_naive();
+    let rows = app.visible().into_iter().map(|status| {
+        let favourite = if app.favourites.contains(&status.kind) {
+            "*"
+        } else {
+            " "
+        };
+        let (active, ends) = match &status.active {
+            Some(event) => (
+                event.schedule.name,
+                countdown(event.end_datetime(date) - app.now),
+            ),
+            None => ("", String::new()),
+        };
+        let (next, starts) = match &status.next {
+            Some(event) => (
+                event.schedule.name,
+                countdown(event.start_datetime(date) - app.now),
+            ),
+            None => ("", String::new()),
+        };
+        Row::new(vec![
+            Cell::from(favourite),
+            Cell::from(status.kind.info().name),
+            Cell::from(active),
+            Cell::from(ends),
+            Cell::from(next),
+            Cell::from(starts),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Fill(2),
+            Constraint::Fill(3),
+            Constraint::Length(9),
+            Constraint::Fill(3),
+            Constraint::Length(9),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "Meta", "Active", "Ends in", "Next", "Starts in"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = TableState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(table, body, &mut state);
+
+    frame.render_widget(
+        Line::from("q quit  j/k move  f favourite  F favourites only  c category"),
+        footer,
+    );
+}
+
+/// Format a countdown like `1:05:09`
+fn countdown(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    format!(
+        "{}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Show the dashboard in the terminal until the user quits
+pub fn run(clock: &impl Clock, tz: &Tz, favourites: &[MapMetaKind]) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let mut app = App::new(*tz, favourites);
+    let result = loop {
+        app.update(clock.now());
+        if let Err(error) = terminal.draw(|frame| render(frame, &app)) {
+            break Err(error);
+        }
+
+        match event::poll(StdDuration::from_secs(1)) {
+            Ok(true) => match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if app.handle_key(key.code) {
+                        break Ok(());
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => break Err(error),
+            },
+            Ok(false) => {}
+            Err(error) => break Err(error),
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use gw2timers::meta::MapMetaKind;
+    use ratatui::{backend::TestBackend, crossterm::event::KeyCode, Terminal};
+
+    use super::{render, App};
+
+    fn draw(app: &App) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(100, 8)).unwrap();
+        terminal.draw(|frame| render(frame, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_render() {
+        let mut app = App::new(chrono_tz::UTC, &[MapMetaKind::WorldBosses]);
+        app.update(Utc.with_ymd_and_hms(2022, 3, 1, 8, 41, 30).unwrap());
+        assert_eq!(
+            draw(&app),
+            vec![
+                "2022-03-01 08:41:30 UTC  (All categories)",
+                "  Meta                Active                        Ends in   Next                         Starts in",
+                "  Day and Night       Day                           0:58:30   Dusk                         0:58:30",
+                "* World Bosses        Claw of Jormag                0:03:30   Fire Elemental               0:03:30",
+                "  Hard World Bosses                                           Karka Queen                  1:48:30",
+                "  Ley-Line Anomaly                                            Gendarran Fields             1:38:30",
+                "  Twisted Marionette                                          Twisted Marionette (Public)  1:18:30",
+                "q quit  j/k move  f favourite  F favourites only  c category",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let mut app = App::new(chrono_tz::UTC, &[]);
+        app.update(Utc.with_ymd_and_hms(2022, 3, 1, 8, 41, 30).unwrap());
+
+        app.handle_key(KeyCode::Char('c'));
+        app.handle_key(KeyCode::Char('c'));
+        app.handle_key(KeyCode::Char('c'));
+        let lines = draw(&app);
+        assert_eq!(lines[0], "2022-03-01 08:41:30 UTC  (Heart of Thorns)");
+        assert!(lines[2].starts_with("  Verdant Brink"));
+
+        app.handle_key(KeyCode::Char('j'));
+        app.handle_key(KeyCode::Char('f'));
+        app.handle_key(KeyCode::Char('F'));
+        let lines = draw(&app);
+        assert_eq!(
+            lines[0],
+            "2022-03-01 08:41:30 UTC  (Heart of Thorns, favourites only)"
+        );
+        assert!(lines[2].starts_with("* Auric Basin"));
+        assert_eq!(lines[3], "");
+
+        assert!(!app.handle_key(KeyCode::Char('x')));
+        assert!(app.handle_key(KeyCode::Char('q')));
+    }
+}
@@ -0,0 +1,542 @@
+//! Query Guild Wars 2 map meta event times from the command line
+
+#[cfg(feature = "tui")]
+mod dashboard;
+
+use std::{
+    convert::TryFrom,
+    io::{self, Write},
+    process,
+};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
+use clap::{Args, Parser, Subcommand};
+use serde::Serialize;
+
+use gw2timers::{
+    category::Category,
+    clock::{Clock, FixedClock, SystemClock},
+    event::EventInstance,
+    humanize::Humanizer,
+    meta::MapMetaKind,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "gw2timers",
+    version,
+    about = "Guild Wars 2 map meta event times"
+)]
+struct Cli {
+    /// The time to look at instead of now, e.g. `18:30`, `2022-03-01 18:30` or RFC 3339
+    #[arg(long, global = true, value_name = "TIME")]
+    at: Option<String>,
+
+    /// The time zone to read and show times in
+    #[arg(long, global = true, default_value = "UTC", value_parser = parse_tz)]
+    tz: Tz,
+
+    /// Print results as JSON
+    #[arg(long, global = true)]
+    json: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Show the events that are active
+    Active {
+        #[command(flatten)]
+        filter: Filter,
+
+        /// Only show events whose name contains this
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Show the next events to start
+    Next {
+        /// How many events to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        count: usize,
+
+        #[command(flatten)]
+        filter: Filter,
+
+        /// Only show events whose name contains this
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Show every event starting on a day
+    Timetable {
+        /// The day to show, defaults to the day of `--at`
+        #[arg(long)]
+        date: Option<NaiveDate>,
+
+        #[command(flatten)]
+        filter: Filter,
+
+        /// Only show events whose name contains this
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Show how long until an event starts
+    Until {
+        /// The name, or part of the name, of the event
+        event: String,
+
+        #[command(flatten)]
+        filter: Filter,
+    },
+
+    /// Show a live dashboard of every map meta
+    #[cfg(feature = "tui")]
+    Dashboard {
+        /// Mark a map meta as a favourite, may be given more than once
+        #[arg(long = "favourite", value_name = "META")]
+        favourites: Vec<MapMetaKind>,
+    },
+
+    /// Serve map meta event times as JSON over HTTP
+    #[cfg(feature = "server")]
+    Serve {
+        /// The address to listen on
+        #[arg(long, default_value = "127.0.0.1:8080")]
+        addr: std::net::SocketAddr,
+    },
+}
+
+#[derive(Args)]
+struct Filter {
+    /// Only show events from this map meta, may be given more than once
+    #[arg(long = "meta", value_name = "META")]
+    metas: Vec<MapMetaKind>,
+
+    /// Only show events from map metas in this category, may be given more than once
+    #[arg(long = "category", value_name = "CATEGORY")]
+    categories: Vec<Category>,
+}
+
+impl Filter {
+    /// Get the map metas that match this filter
+    fn metas(&self) -> Vec<MapMetaKind> {
+        MapMetaKind::all_keys()
+            .iter()
+            .copied()
+            .filter(|kind| self.metas.is_empty() || self.metas.contains(kind))
+            .filter(|kind| {
+                self.categories.is_empty() || self.categories.contains(&kind.info().category)
+            })
+            .collect()
+    }
+}
+
+/// A single event to show
+#[derive(Serialize)]
+struct Row {
+    meta: &'static str,
+    category: &'static str,
+    event: &'static str,
+    start: DateTime<Tz>,
+    end: DateTime<Tz>,
+}
+
+impl Row {
+    fn new(kind: MapMetaKind, event: &EventInstance, date: NaiveDate, tz: &Tz) -> Self {
+        let meta = kind.info();
+        Row {
+            meta: meta.name,
+            category: meta.category.name(),
+            event: event.schedule.name,
+            start: event.start_datetime(date).with_timezone(tz),
+            end: event.end_datetime(date).with_timezone(tz),
+        }
+    }
+}
+
+/// How long until an event starts, or ends if it's already active
+#[derive(Serialize)]
+struct Until {
+    #[serde(flatten)]
+    row: Row,
+    active: bool,
+    minutes: i64,
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(error) = run(&cli, &SystemClock, &mut io::stdout().lock()) {
+        eprintln!("error: {}", error);
+        process::exit(1);
+    }
+}
+
+fn run(cli: &Cli, clock: &impl Clock, out: &mut impl Write) -> Result<(), String> {
+    let at = match &cli.at {
+        Some(at) => parse_at(at, &cli.tz, clock.now())?,
+        None => clock.now(),
+    };
+    // The dashboard keeps running, so it follows the real clock unless a time was given
+    #[cfg(feature = "tui")]
+    if let (Command::Dashboard { favourites }, None) = (&cli.command, &cli.at) {
+        return dashboard::run(clock, &cli.tz, favourites).map_err(|e| e.to_string());
+    }
+
+    let clock = FixedClock::new(at);
+
+    match &cli.command {
+        Command::Active { filter, name } => {
+            let rows = filter
+                .metas()
+                .into_iter()
+                .flat_map(|kind| {
+                    kind.info()
+                        .schedules
+                        .iter()
+                        .filter(|schedule| matches(schedule.name, name))
+                        .filter_map(|schedule| schedule.iter_from(&clock).now())
+                        .map(|event| Row::new(kind, &event, at.date_naive(), &cli.tz))
+                        .collect::<Vec<Row>>()
+                })
+                .collect::<Vec<Row>>();
+            print_rows(cli, rows, out)
+        }
+        Command::Next {
+            count,
+            filter,
+            name,
+        } => {
+            // Every event happens at least once a day, so this is always far enough to find them
+            let start = Duration::seconds(at.time().num_seconds_from_midnight() as i64);
+            let end = i64::try_from((*count).max(1))
+                .ok()
+                .and_then(Duration::try_days)
+                .and_then(|horizon| start.checked_add(&horizon))
+                .ok_or_else(|| format!("can't look {} events ahead", count))?;
+            let mut rows = filter
+                .metas()
+                .into_iter()
+                .flat_map(|kind| {
+                    kind.iter_from(&clock)
+                        .take_while(|event| event.start_time < end)
+                        .filter(|event| matches(event.schedule.name, name))
+                        .take(*count)
+                        .map(|event| Row::new(kind, &event, at.date_naive(), &cli.tz))
+                        .collect::<Vec<Row>>()
+                })
+                .collect::<Vec<Row>>();
+            rows.sort_by_key(|row| row.start);
+            rows.truncate(*count);
+            print_rows(cli, rows, out)
+        }
+        Command::Timetable { date, filter, name } => {
+            let date = date.unwrap_or_else(|| at.with_timezone(&cli.tz).date_naive());
+            let day_start = local_datetime(&cli.tz, date.and_time(NaiveTime::MIN))?;
+            let next_day = date
+                .succ_opt()
+                .ok_or_else(|| format!("no timetable after {}", date))?;
+            let day_end = local_datetime(&cli.tz, next_day.and_time(NaiveTime::MIN))?;
+
+            // Start a minute early so events starting right at midnight are included
+            let start = day_start
+                .checked_sub_signed(Duration::minutes(1))
+                .ok_or_else(|| format!("no timetable before {}", date))?;
+            let clock = FixedClock::new(start);
+            let mut rows = filter
+                .metas()
+                .into_iter()
+                .flat_map(|kind| {
+                    kind.iter_from(&clock)
+                        .take_while(|event| event.start_datetime(start.date_naive()) < day_end)
+                        .filter(|event| matches(event.schedule.name, name))
+                        .map(|event| Row::new(kind, &event, start.date_naive(), &cli.tz))
+                        .collect::<Vec<Row>>()
+                })
+                .collect::<Vec<Row>>();
+            rows.sort_by_key(|row| row.start);
+            print_rows(cli, rows, out)
+        }
+        Command::Until { event, filter } => {
+            let name = Some(event.clone());
+            let mut found: Option<Until> = None;
+            for kind in filter.metas() {
+                for schedule in kind.info().schedules.iter() {
+                    if !matches(schedule.name, &name) {
+                        continue;
+                    }
+
+                    let until = match schedule.iter_from(&clock).now() {
+                        Some(event) => Until {
+                            row: Row::new(kind, &event, at.date_naive(), &cli.tz),
+                            active: true,
+                            minutes: minutes_until(event.end_datetime(at.date_naive()), at),
+                        },
+                        None => match schedule.iter_from(&clock).next() {
+                            Some(event) => Until {
+                                row: Row::new(kind, &event, at.date_naive(), &cli.tz),
+                                active: false,
+                                minutes: minutes_until(event.start_datetime(at.date_naive()), at),
+                            },
+                            None => continue,
+                        },
+                    };
+
+                    // Prefer an active event, then whichever starts soonest
+                    let better = match &found {
+                        Some(found) => {
+                            (!found.active && until.active)
+                                || (found.active == until.active
+                                    && until.row.start < found.row.start)
+                        }
+                        None => true,
+                    };
+                    if better {
+                        found = Some(until);
+                    }
+                }
+            }
+
+            let until = found.ok_or_else(|| format!("no event found matching '{}'", event))?;
+            if cli.json {
+                let json = serde_json::to_string_pretty(&until).map_err(|e| e.to_string())?;
+                writeln!(out, "{}", json).map_err(|e| e.to_string())
+            } else {
+                let state = if until.active { "ends" } else { "starts" };
+                let time = if until.active {
+                    until.row.end
+                } else {
+                    until.row.start
+                };
+                writeln!(
+                    out,
+                    "{} ({}) {} in {} at {}",
+                    until.row.event,
+                    until.row.meta,
+                    state,
+                    Humanizer::default().duration(Duration::minutes(until.minutes)),
+                    time.format("%Y-%m-%d %H:%M %Z")
+                )
+                .map_err(|e| e.to_string())
+            }
+        }
+        #[cfg(feature = "tui")]
+        Command::Dashboard { favourites } => {
+            dashboard::run(&clock, &cli.tz, favourites).map_err(|e| e.to_string())
+        }
+        #[cfg(feature = "server")]
+        Command::Serve { addr } => {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| e.to_string())?;
+            let served = match cli.at {
+                Some(_) => runtime.block_on(gw2timers::server::serve(*addr, clock)),
+                None => runtime.block_on(gw2timers::server::serve(*addr, SystemClock)),
+            };
+            served.map_err(|e| e.to_string())
+        }
+    }
+}
+
+fn print_rows(cli: &Cli, rows: Vec<Row>, out: &mut impl Write) -> Result<(), String> {
+    if cli.json {
+        let json = serde_json::to_string_pretty(&rows).map_err(|e| e.to_string())?;
+        return writeln!(out, "{}", json).map_err(|e| e.to_string());
+    }
+
+    for row in rows {
+        writeln!(
+            out,
+            "{} - {}  {}: {}",
+            row.start.format("%Y-%m-%d %H:%M"),
+            row.end.format("%H:%M %Z"),
+            row.meta,
+            row.event
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// The number of minutes from `at` until `time`, counting any part of a minute as a whole one
+fn minutes_until(time: DateTime<Utc>, at: DateTime<Utc>) -> i64 {
+    let until = time - at;
+    let minutes = until.num_minutes();
+    if until > Duration::minutes(minutes) {
+        minutes + 1
+    } else {
+        minutes
+    }
+}
+
+/// Whether the event `name` contains the `filter`, ignoring case
+fn matches(name: &str, filter: &Option<String>) -> bool {
+    match filter {
+        Some(filter) => name.to_lowercase().contains(&filter.to_lowercase()),
+        None => true,
+    }
+}
+
+fn parse_tz(value: &str) -> Result<Tz, String> {
+    value.parse::<Tz>().map_err(|e| e.to_string())
+}
+
+/// Parse the `--at` time, where times without a date are on the current day in `tz`
+fn parse_at(value: &str, tz: &Tz, now: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+
+    for format in ["%Y-%m-%d %H:%M", "%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M"] {
+        if let Ok(time) = NaiveDateTime::parse_from_str(value, format) {
+            return local_datetime(tz, time);
+        }
+    }
+
+    for format in ["%H:%M", "%H:%M:%S"] {
+        if let Ok(time) = NaiveTime::parse_from_str(value, format) {
+            let today = now.with_timezone(tz).date_naive();
+            return local_datetime(tz, today.and_time(time));
+        }
+    }
+
+    Err(format!("invalid time '{}'", value))
+}
+
+fn local_datetime(tz: &Tz, time: NaiveDateTime) -> Result<DateTime<Utc>, String> {
+    tz.from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+        .ok_or_else(|| format!("{} does not exist in {}", time, tz))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use clap::Parser;
+    use gw2timers::clock::FixedClock;
+
+    use super::{run, Cli};
+
+    fn result(args: &[&str]) -> Result<String, String> {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2022, 3, 1, 8, 41, 0).unwrap());
+        let cli = Cli::parse_from(std::iter::once("gw2timers").chain(args.iter().copied()));
+        let mut out = Vec::new();
+        run(&cli, &clock, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    fn output(args: &[&str]) -> String {
+        result(args).unwrap()
+    }
+
+    #[test]
+    fn test_next() {
+        assert_eq!(
+            output(&["next", "-n", "3", "--meta", "world bosses"]),
+            "2022-03-01 08:45 - 09:00 UTC  World Bosses: Fire Elemental\n\
+             2022-03-01 09:00 - 09:15 UTC  World Bosses: Admiral Taidha Covington\n\
+             2022-03-01 09:15 - 09:30 UTC  World Bosses: Great Jungle Wurm\n"
+        );
+        assert_eq!(
+            output(&[
+                "next",
+                "-n",
+                "1",
+                "--name",
+                "tequatl",
+                "--tz",
+                "Europe/Berlin"
+            ]),
+            "2022-03-01 12:30 - 13:00 CET  Hard World Bosses: Tequatl the Sunless\n"
+        );
+
+        let count = usize::MAX.to_string();
+        assert_eq!(
+            result(&["next", "-n", &count]),
+            Err(format!("can't look {} events ahead", count))
+        );
+    }
+
+    #[test]
+    fn test_active() {
+        assert_eq!(
+            output(&["--at", "00:25", "active", "--category", "end-of-dragons"]),
+            "2022-03-01 00:25 - 00:30 UTC  Cantha: Day and Night: Dawn\n\
+             2022-03-01 00:00 - 00:40 UTC  New Kaineng City: Kaineng Blackout\n\
+             2022-03-01 00:13 - 00:45 UTC  Dragon's End: Preparations\n"
+        );
+
+        // Night started the day before
+        assert_eq!(
+            output(&["--at", "00:10", "active", "--meta", "day and night"]),
+            "2022-02-28 23:45 - 00:25 UTC  Day and Night: Night\n"
+        );
+    }
+
+    #[test]
+    fn test_timetable() {
+        let timetable = output(&[
+            "timetable",
+            "--meta",
+            "HardWorldBosses",
+            "--date",
+            "2022-03-02",
+        ]);
+        assert_eq!(timetable.lines().count(), 18);
+        assert!(timetable.starts_with("2022-03-02 00:00 - 00:30 UTC  Hard World Bosses: Tequatl"));
+
+        assert_eq!(
+            result(&["timetable", "--date", "+262142-12-31"]),
+            Err(String::from("no timetable after +262142-12-31"))
+        );
+        assert_eq!(
+            result(&["timetable", "--date=-262143-01-01"]),
+            Err(String::from("no timetable before -262143-01-01"))
+        );
+    }
+
+    #[test]
+    fn test_until() {
+        assert_eq!(
+            output(&["until", "Tequatl"]),
+            "Tequatl the Sunless (Hard World Bosses) starts in 2h 49m at 2022-03-01 11:30 UTC\n"
+        );
+        assert_eq!(
+            output(&["--at", "2022-03-01T11:40:00Z", "until", "tequatl"]),
+            "Tequatl the Sunless (Hard World Bosses) ends in 20m at 2022-03-01 12:00 UTC\n"
+        );
+
+        // Part of a minute left counts as a whole one
+        assert_eq!(
+            output(&["--at", "2022-03-01T11:29:01Z", "until", "tequatl"]),
+            "Tequatl the Sunless (Hard World Bosses) starts in 1m at 2022-03-01 11:30 UTC\n"
+        );
+        assert_eq!(
+            output(&["--at", "2022-03-01T11:40:30Z", "until", "tequatl"]),
+            "Tequatl the Sunless (Hard World Bosses) ends in 20m at 2022-03-01 12:00 UTC\n"
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let json: serde_json::Value = serde_json::from_str(&output(&[
+            "--json",
+            "next",
+            "-n",
+            "2",
+            "--meta",
+            "auric basin",
+        ]))
+        .unwrap();
+        assert_eq!(json[0]["meta"], "Auric Basin");
+        assert_eq!(json[0]["event"], "Challenges");
+        assert_eq!(json[0]["start"], "2022-03-01T08:45:00Z");
+        assert_eq!(json[1]["event"], "Octovine");
+    }
+}
@@ -1,3 +1,11 @@
+use core::{fmt, str::FromStr};
+
+use alloc::format;
+
+use crate::meta::loose_name;
+
+/// The release a map meta belongs to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Category {
     CoreTyria,
     LivingWorldSeason2,
@@ -8,3 +16,63 @@ pub enum Category {
     TheIcebroodSaga,
     EndOfDragons,
 }
+
+impl Category {
+    /// Get all the categories available
+    pub fn all() -> [Category; 8] {
+        [
+            Category::CoreTyria,
+            Category::LivingWorldSeason2,
+            Category::HeartOfThorns,
+            Category::LivingWorldSeason3,
+            Category::PathOfFire,
+            Category::LivingWorldSeason4,
+            Category::TheIcebroodSaga,
+            Category::EndOfDragons,
+        ]
+    }
+
+    /// The display name of the category
+    pub fn name(&self) -> &'static str {
+        match self {
+            Category::CoreTyria => "Core Tyria",
+            Category::LivingWorldSeason2 => "Living World Season 2",
+            Category::HeartOfThorns => "Heart of Thorns",
+            Category::LivingWorldSeason3 => "Living World Season 3",
+            Category::PathOfFire => "Path of Fire",
+            Category::LivingWorldSeason4 => "Living World Season 4",
+            Category::TheIcebroodSaga => "The Icebrood Saga",
+            Category::EndOfDragons => "End of Dragons",
+        }
+    }
+}
+
+impl FromStr for Category {
+    type Err = ParseCategoryError;
+
+    /// Find a category by its name or variant name, ignoring case, spaces and punctuation
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = loose_name(s);
+        Category::all()
+            .iter()
+            .copied()
+            .find(|category| {
+                loose_name(category.name()) == name
+                    || loose_name(&format!("{:?}", category)) == name
+            })
+            .ok_or(ParseCategoryError)
+    }
+}
+
+/// The error given when parsing an unknown [Category]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseCategoryError;
+
+impl fmt::Display for ParseCategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown category")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseCategoryError {}
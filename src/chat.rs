@@ -0,0 +1,203 @@
+//! Formats [EventInstance]s as chat messages
+//!
+//! A [Formatter] writes events for a chat [Platform], using its markup for times so they show in
+//! each reader's own time zone where possible. Each event is written using a template line, where
+//! these placeholders are replaced:
+//!
+//! - `{name}`: the name of the event
+//! - `{start}`: the time the event starts
+//! - `{end}`: the time the event ends
+//! - `{relative}`: how long until the event starts, e.g. `in 12m` or `started 3m ago`
+//! - `{ends}`: how long until the event ends, e.g. `ends in 27m`
+//! - `{length}`: how long the event lasts, e.g. `1h 10m`
+//!
+//! Text is written in the formatter's [Language]. Discord shows relative times itself, in each
+//! reader's own language, so they're only the timestamp there.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use crate::{
+    clock::FixedClock,
+    event::EventInstance,
+    humanize::{Humanizer, Language},
+    meta::MapMetaKind,
+};
+
+/// A chat platform to format messages for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Platform {
+    /// Discord markdown with `<t:...>` timestamps
+    Discord,
+
+    /// Slack mrkdwn with `<!date^...>` timestamps
+    Slack,
+
+    /// Plain text with UTC times
+    Plain,
+}
+
+/// Writes [EventInstance]s as messages for a chat [Platform]
+pub struct Formatter {
+    platform: Platform,
+
+    /// The time right now, which the formatted events were iterated from
+    now: DateTime<Utc>,
+
+    /// The title of messages
+    title: Option<String>,
+
+    /// The template each event is written with
+    line: String,
+
+    /// Writes lengths of time and countdowns in the chosen language
+    humanizer: Humanizer,
+}
+
+impl Formatter {
+    /// Create a formatter for events from iterators started at `now`, like with
+    /// [MapMetaKind::iter_from]
+    pub fn new(platform: Platform, now: DateTime<Utc>) -> Self {
+        Formatter {
+            platform,
+            now,
+            title: None,
+            line: String::from("{name} {relative}"),
+            humanizer: Humanizer::default(),
+        }
+    }
+
+    /// Set the title of messages, e.g. `Next 5 world bosses`
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Set the template each event is written with
+    pub fn line(mut self, template: &str) -> Self {
+        self.line = template.to_string();
+        self
+    }
+
+    /// Set the language lengths of time and countdowns are written in
+    ///
+    /// The title and line template are written as given, so should be in the same language.
+    pub fn language(mut self, language: Language) -> Self {
+        self.humanizer = Humanizer::new(language);
+        self
+    }
+
+    fn date(&self) -> NaiveDate {
+        self.now.date_naive()
+    }
+
+    /// Write a single event using the line template
+    pub fn event(&self, event: &EventInstance) -> String {
+        let start = event.start_datetime(self.date());
+        let end = event.end_datetime(self.date());
+        self.line
+            .replace("{name}", event.schedule.name)
+            .replace("{start}", &self.time(start))
+            .replace("{end}", &self.time(end))
+            .replace("{relative}", &self.relative(start, Humanizer::starts))
+            .replace("{ends}", &self.relative(end, Humanizer::ends))
+            .replace("{length}", &self.humanizer.duration(event.schedule.length))
+    }
+
+    /// Write a message with the title followed by a line for each event
+    pub fn message(&self, events: &[EventInstance]) -> String {
+        let mut lines = Vec::new();
+        if let Some(title) = &self.title {
+            lines.push(self.bold(title));
+        }
+        lines.extend(events.iter().map(|event| self.event(event)));
+        lines.join("\n")
+    }
+
+    /// Write a one line summary of what's active in a map meta and what's next
+    pub fn meta_summary(&self, kind: MapMetaKind) -> String {
+        let mut iter = kind.iter_from(&FixedClock::new(self.now));
+        let mut summary = format!("{}:", self.bold(kind.info().name));
+        if let Some(active) = iter.now() {
+            summary.push_str(&format!(
+                " {} {},",
+                active.schedule.name,
+                self.relative(active.end_datetime(self.date()), Humanizer::ends)
+            ));
+        }
+        if let Some(next) = iter.next() {
+            summary.push_str(&format!(
+                " {} {}",
+                self.humanizer.next(next.schedule.name),
+                self.relative(next.start_datetime(self.date()), Humanizer::starts)
+            ));
+        }
+        summary
+    }
+
+    /// Create a Discord embed object with a field for each event
+    ///
+    /// Only available with the `json` feature enabled.
+    #[cfg(feature = "json")]
+    pub fn discord_embed(&self, events: &[EventInstance]) -> serde_json::Value {
+        let formatter = Formatter {
+            platform: Platform::Discord,
+            now: self.now,
+            title: None,
+            line: self.line.clone(),
+            humanizer: self.humanizer,
+        };
+        let fields = events
+            .iter()
+            .map(|event| {
+                serde_json::json!({
+                    "name": event.schedule.name,
+                    "value": formatter.event(event),
+                    "inline": false,
+                })
+            })
+            .collect::<Vec<serde_json::Value>>();
+
+        let mut embed = serde_json::json!({ "fields": fields });
+        if let Some(title) = &self.title {
+            embed["title"] = serde_json::Value::from(title.as_str());
+        }
+        embed
+    }
+
+    fn bold(&self, text: &str) -> String {
+        match self.platform {
+            Platform::Discord => format!("**{}**", text),
+            Platform::Slack => format!("*{}*", text),
+            Platform::Plain => text.to_string(),
+        }
+    }
+
+    fn time(&self, time: DateTime<Utc>) -> String {
+        let fallback = time.format("%H:%M UTC");
+        match self.platform {
+            Platform::Discord => format!("<t:{}:t>", time.timestamp()),
+            Platform::Slack => format!("<!date^{}^{{time}}|{}>", time.timestamp(), fallback),
+            Platform::Plain => fallback.to_string(),
+        }
+    }
+
+    /// Write how long until `time`, with `countdown` for platforms without relative timestamps
+    fn relative(
+        &self,
+        time: DateTime<Utc>,
+        countdown: fn(&Humanizer, Duration) -> String,
+    ) -> String {
+        let fallback = countdown(&self.humanizer, time - self.now);
+        match self.platform {
+            Platform::Discord => format!("<t:{}:R>", time.timestamp()),
+            Platform::Slack => format!("<!date^{}^{{ago}}|{}>", time.timestamp(), fallback),
+            Platform::Plain => fallback,
+        }
+    }
+}
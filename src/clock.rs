@@ -0,0 +1,88 @@
+//! Sources of the current time
+
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+#[cfg(feature = "std")]
+use chrono::Duration;
+use chrono::{DateTime, Utc};
+
+/// Something that can tell the current time
+pub trait Clock {
+    /// The time right now
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// A [Clock] that reads the system's time
+///
+/// Only available with the `std` feature enabled.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A [Clock] that is stopped at a certain time
+#[derive(Clone, Copy, Debug)]
+pub struct FixedClock {
+    time: DateTime<Utc>,
+}
+
+impl FixedClock {
+    pub fn new(time: DateTime<Utc>) -> Self {
+        FixedClock { time }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.time
+    }
+}
+
+/// A [Clock] that only moves when it's told to
+///
+/// Only available with the `std` feature enabled.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct ManualClock {
+    time: Mutex<DateTime<Utc>>,
+}
+
+#[cfg(feature = "std")]
+impl ManualClock {
+    pub fn new(time: DateTime<Utc>) -> Self {
+        ManualClock {
+            time: Mutex::new(time),
+        }
+    }
+
+    /// Move the clock to a certain time
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.time.lock().unwrap() = time;
+    }
+
+    /// Move the clock forward an amount of time
+    pub fn advance(&self, amount: Duration) {
+        let mut time = self.time.lock().unwrap();
+        *time += amount;
+    }
+}
+
+#[cfg(feature = "std")]
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time.lock().unwrap()
+    }
+}
@@ -0,0 +1,87 @@
+//! Finds which of a selection of events can't both be attended
+//!
+//! A [ConflictChecker] is given the [Target]s someone wants to attend, and reports every pair of
+//! their occurrences within a window of time that overlap, e.g. Tequatl against the Dragon's End
+//! battle. Each [Conflict] suggests other occurrences of the same events that avoid it.
+
+use alloc::vec::Vec;
+
+use chrono::Duration;
+
+use crate::route::{Stop, Target};
+
+/// Two occurrences of different [Target]s that overlap
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    /// The occurrence that sets off first
+    pub first: Stop,
+
+    pub second: Stop,
+
+    /// How long both are active for, including the time to travel to each
+    pub overlap: Duration,
+
+    /// Other occurrences of the first target within the window that don't overlap `second`
+    pub first_alternatives: Vec<Stop>,
+
+    /// Other occurrences of the second target within the window that don't overlap `first`
+    pub second_alternatives: Vec<Stop>,
+}
+
+/// Checks a selection of [Target]s for occurrences that overlap
+///
+/// The value of each target isn't used, but its travel time is counted as part of the event.
+pub struct ConflictChecker {
+    pub targets: Vec<Target>,
+}
+
+impl ConflictChecker {
+    pub fn new(targets: Vec<Target>) -> Self {
+        ConflictChecker { targets }
+    }
+
+    /// Find every conflict between occurrences starting from `start` until `end`, from UTC 00:00
+    ///
+    /// Conflicts are in order of when their first occurrence sets off. Occurrences of the same
+    /// target never conflict with each other.
+    pub fn check(&self, start: Duration, end: Duration) -> Vec<Conflict> {
+        let mut stops = self
+            .targets
+            .iter()
+            .enumerate()
+            .flat_map(|(index, target)| target.stops(index, start, end))
+            .collect::<Vec<Stop>>();
+        stops.sort_by_key(|stop| (stop.depart, stop.target));
+
+        let alternatives = |original: &Stop, avoid: &Stop| {
+            stops
+                .iter()
+                .filter(|stop| stop.target == original.target)
+                .filter(|stop| stop.event.start_time != original.event.start_time)
+                .filter(|stop| stop.depart >= start && stop.end_time() <= end)
+                .filter(|stop| !stop.conflicts(avoid))
+                .cloned()
+                .collect::<Vec<Stop>>()
+        };
+
+        let mut conflicts = Vec::new();
+        for (i, first) in stops.iter().enumerate() {
+            for second in stops[i + 1..]
+                .iter()
+                .take_while(|second| second.depart < first.end_time())
+            {
+                if second.target == first.target || !first.conflicts(second) {
+                    continue;
+                }
+                conflicts.push(Conflict {
+                    first: first.clone(),
+                    second: second.clone(),
+                    overlap: first.end_time().min(second.end_time()) - second.depart,
+                    first_alternatives: alternatives(first, second),
+                    second_alternatives: alternatives(second, first),
+                });
+            }
+        }
+        conflicts
+    }
+}
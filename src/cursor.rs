@@ -0,0 +1,134 @@
+//! A cursor over the occurrences of one or more [EventSchedule](crate::schedule::EventSchedule)s
+//!
+//! Every occurrence is numbered in order of start time, so a [Cursor] can move to any time, look
+//! ahead, step back, or skip any number of events without walking through them.
+
+use core::convert::TryFrom;
+
+use alloc::borrow::Cow;
+
+use chrono::{Duration, NaiveTime, Timelike};
+
+#[cfg(feature = "tokio")]
+use crate::stream::EventStream;
+use crate::{cycle::CycleTable, event::EventInstance};
+
+/// An iterator that gives the next occurring [EventInstance] after its current time
+///
+/// The first call to `next()` gives the first event to start after the time the cursor was moved
+/// to. Events active at that time are given by [Cursor::now] instead.
+#[derive(Clone, Debug)]
+pub struct Cursor {
+    table: Cow<'static, CycleTable>,
+
+    /// Respresents the amount of time from UTC 00:00
+    current_time: Duration,
+
+    /// The number of the occurrence `next()` gives, counting from the first one after UTC 00:00
+    index: i64,
+}
+
+impl Cursor {
+    /// Create a cursor at UTC 00:00 over every occurrence in `table`
+    pub fn new(table: Cow<'static, CycleTable>) -> Self {
+        let mut cursor = Cursor {
+            table,
+            current_time: Duration::zero(),
+            index: 0,
+        };
+        cursor.seek(Duration::zero());
+        cursor
+    }
+
+    /// Skip to a certain time of day
+    pub fn time(mut self, time: NaiveTime) -> Self {
+        self.seek(Duration::seconds(time.num_seconds_from_midnight() as i64));
+        self
+    }
+
+    /// Skip forward an amount of time, or back when `amount` is negative
+    pub fn fast_forward(mut self, amount: Duration) -> Self {
+        // Past the range of a Duration there are no events, so stop at the end of it
+        let furthest = Duration::milliseconds(i64::MAX);
+        let time = match self.current_time.checked_add(&amount) {
+            Some(time) => time,
+            None if amount < Duration::zero() => -furthest,
+            None => furthest,
+        };
+        self.seek(time);
+        self
+    }
+
+    /// Move to `time` from UTC 00:00, which may be on another day or before it
+    pub fn seek(&mut self, time: Duration) {
+        self.current_time = time;
+        self.index = self.table.index_after(time);
+    }
+
+    /// The time from UTC 00:00 the cursor is at
+    ///
+    /// Moving to an event with `next()` moves the cursor to the start of that event, and
+    /// [Cursor::step_back] moves it to the minute before the event, as if seeking there.
+    pub fn position(&self) -> Duration {
+        self.current_time
+    }
+
+    /// Get the event happening at the cursor's current time, if any
+    pub fn now(&self) -> Option<EventInstance> {
+        self.table.now(self.current_time)
+    }
+
+    /// Get the event `next()` would give, without moving
+    pub fn peek(&self) -> Option<EventInstance> {
+        self.table.occurrence(self.index)
+    }
+
+    /// Move back to the last event given by `next()`, and give it again
+    ///
+    /// Calling this repeatedly walks backwards through earlier events.
+    pub fn step_back(&mut self) -> Option<EventInstance> {
+        let index = self.index.checked_sub(1)?;
+        let event = self.table.occurrence(index)?;
+        self.index = index;
+        self.current_time = event
+            .start_time
+            .checked_sub(&Duration::minutes(1))
+            .unwrap_or_else(|| -Duration::milliseconds(i64::MAX));
+        Some(event)
+    }
+
+    /// Turn this cursor into a [Stream](futures_core::Stream) that yields each event as it starts
+    ///
+    /// The cursor's current time is taken to be the time right now.
+    #[cfg(feature = "tokio")]
+    pub fn into_stream(self) -> EventStream<Self> {
+        let time = self.current_time;
+        EventStream::new(self, time)
+    }
+}
+
+impl Iterator for Cursor {
+    type Item = EventInstance;
+
+    fn next(&mut self) -> Option<EventInstance> {
+        self.nth(0)
+    }
+
+    /// Skip `n` events and give the one after, without looking at the skipped events
+    fn nth(&mut self, n: usize) -> Option<EventInstance> {
+        let index = self.index.checked_add(i64::try_from(n).ok()?)?;
+        let event = self.table.occurrence(index)?;
+        self.index = index.checked_add(1)?;
+        self.current_time = event.start_time;
+        Some(event)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Events stop at the end of the range of a Duration, so there may be none left
+        if self.table.is_empty() {
+            (0, Some(0))
+        } else {
+            (0, None)
+        }
+    }
+}
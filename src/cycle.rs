@@ -0,0 +1,304 @@
+//! Precomputed tables of every event occurrence in a repeating set of [EventSchedule]s
+//!
+//! A set of schedules repeats itself every time the least common multiple of their frequencies has
+//! passed. A [CycleTable] lists every occurrence within one of those periods, sorted by start time,
+//! so finding the next or active event is a binary search instead of a walk over every schedule.
+//! When the period is too long to list, the schedules are merged instead, each event being the
+//! earliest of every schedule's next occurrence.
+
+use core::convert::TryFrom;
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use chrono::{Duration, Timelike};
+
+use crate::{
+    error::Error,
+    event::EventInstance,
+    schedule::{EventSchedule, MAX_LENGTH},
+};
+
+/// A single occurrence of an event within a period
+#[derive(Clone, Copy, Debug)]
+struct Occurrence {
+    /// Minutes from the start of the period
+    start: i64,
+
+    /// The index of the occurrence's schedule
+    schedule: usize,
+}
+
+/// When the events of a schedule start
+#[derive(Clone, Copy, Debug)]
+struct Timing {
+    /// The index of the schedule
+    schedule: usize,
+
+    /// Minutes from UTC 00:00 of the first event, less than the frequency
+    offset: i64,
+
+    /// Minutes between each event
+    frequency: i64,
+}
+
+impl Timing {
+    /// The number of events starting from UTC 00:00 until `time`, or negative for the events
+    /// between `time` and UTC 00:00
+    fn count(&self, time: i64) -> i64 {
+        (time - self.offset).div_euclid(self.frequency) + 1
+    }
+
+    /// The start of the last event starting at or before `time`
+    fn last(&self, time: i64) -> i64 {
+        time - (time - self.offset).rem_euclid(self.frequency)
+    }
+}
+
+/// Every occurrence of a set of [EventSchedule]s over the period they repeat in
+#[derive(Clone, Debug)]
+pub struct CycleTable {
+    schedules: Cow<'static, [EventSchedule]>,
+
+    /// The schedules that can be iterated, in order
+    timings: Vec<Timing>,
+
+    /// The number of minutes before every schedule repeats, if short enough to list every
+    /// occurrence within it
+    period: Option<i64>,
+
+    /// Sorted by start time, then by the order of the schedules. Empty when the schedules are
+    /// merged instead
+    occurrences: Vec<Occurrence>,
+
+    /// The length in minutes of the longest event
+    longest: i64,
+}
+
+/// The longest period a table lists the occurrences of, in minutes
+const MAX_PERIOD: i64 = 4 * 7 * 24 * 60;
+
+/// The furthest time from UTC 00:00 in minutes that events can start at
+const LIMIT: i64 = i64::MAX / 60_000;
+
+impl CycleTable {
+    /// Build the table for a set of schedules
+    ///
+    /// Schedules are counted in whole minutes. When two events start at the same time, the one
+    /// whose schedule comes first is given first. Schedules with a frequency of less than a minute
+    /// or events lasting longer than four weeks can't be iterated, and are left out of the table.
+    /// Use [CycleTable::try_new] to be told about them instead.
+    ///
+    /// If the schedules only repeat after more than four weeks, their occurrences aren't listed
+    /// and each query merges the schedules instead, which is slower for many schedules.
+    pub fn new(schedules: impl Into<Cow<'static, [EventSchedule]>>) -> Self {
+        let schedules = schedules.into();
+        let included = (0..schedules.len())
+            .filter(|&index| {
+                schedules[index].frequency.num_minutes() >= 1
+                    && schedules[index].length.num_minutes() <= MAX_LENGTH
+            })
+            .collect::<Vec<usize>>();
+        let period = included
+            .iter()
+            .try_fold(1, |period, &index| extend_period(period, &schedules[index]));
+        CycleTable::build(schedules, period, included)
+    }
+
+    /// Build the table for a set of schedules, if every schedule is valid
+    pub fn try_new(schedules: impl Into<Cow<'static, [EventSchedule]>>) -> Result<Self, Error> {
+        let schedules = schedules.into();
+        schedules.iter().try_for_each(EventSchedule::check)?;
+        Ok(CycleTable::new(schedules))
+    }
+
+    fn build(
+        schedules: Cow<'static, [EventSchedule]>,
+        period: Option<i64>,
+        included: Vec<usize>,
+    ) -> Self {
+        let timings = included
+            .iter()
+            .map(|&index| {
+                let schedule = &schedules[index];
+                let frequency = schedule.frequency.num_minutes();
+                Timing {
+                    schedule: index,
+                    offset: (schedule.offset.num_seconds_from_midnight() as i64 / 60) % frequency,
+                    frequency,
+                }
+            })
+            .collect::<Vec<Timing>>();
+
+        let mut occurrences = Vec::new();
+        if let Some(period) = period {
+            for timing in &timings {
+                occurrences.extend((0..period / timing.frequency).map(|i| Occurrence {
+                    start: timing.offset + i * timing.frequency,
+                    schedule: timing.schedule,
+                }));
+            }
+            occurrences.sort_by_key(|occurrence| (occurrence.start, occurrence.schedule));
+        }
+        let longest = included
+            .iter()
+            .map(|&index| schedules[index].length.num_minutes())
+            .max()
+            .unwrap_or(0);
+
+        CycleTable {
+            schedules,
+            timings,
+            period,
+            occurrences,
+            longest,
+        }
+    }
+
+    /// The schedules in the table
+    pub fn schedules(&self) -> &[EventSchedule] {
+        &self.schedules
+    }
+
+    /// The amount of time before every schedule repeats, unless it's too long to list the
+    /// occurrences within it
+    pub fn period(&self) -> Option<Duration> {
+        self.period.map(Duration::minutes)
+    }
+
+    /// The length of the longest event
+    pub(crate) fn longest(&self) -> Duration {
+        Duration::minutes(self.longest.max(0))
+    }
+
+    /// Whether there are no events at all
+    pub(crate) fn is_empty(&self) -> bool {
+        self.timings.is_empty()
+    }
+
+    /// The number of the first occurrence to start after `time`, counting from the first
+    /// occurrence after UTC 00:00
+    pub(crate) fn index_after(&self, time: Duration) -> i64 {
+        let time = minutes(time);
+        let period = match self.period {
+            Some(period) => period,
+            None => return self.count(time),
+        };
+        let cycle = time.div_euclid(period);
+        let within = self
+            .occurrences
+            .partition_point(|occurrence| occurrence.start <= time.rem_euclid(period));
+        cycle
+            .saturating_mul(self.occurrences.len() as i64)
+            .saturating_add(within as i64)
+    }
+
+    /// The occurrence numbered `index`, counting from the first occurrence after UTC 00:00
+    pub(crate) fn occurrence(&self, index: i64) -> Option<EventInstance> {
+        let period = match self.period {
+            Some(period) => period,
+            None => return self.merged_occurrence(index),
+        };
+        if self.occurrences.is_empty() {
+            return None;
+        }
+        let len = self.occurrences.len() as i64;
+        let occurrence = &self.occurrences[index.rem_euclid(len) as usize];
+        let start = index
+            .div_euclid(len)
+            .checked_mul(period)?
+            .checked_add(occurrence.start)?;
+        self.instance(occurrence.schedule, start)
+    }
+
+    /// The occurrence numbered `index` when the schedules are merged
+    fn merged_occurrence(&self, index: i64) -> Option<EventInstance> {
+        if self.count(LIMIT) <= index || self.count(-LIMIT - 1) > index {
+            return None;
+        }
+
+        // The first minute by which more than `index` events have started
+        let (mut low, mut high) = (-LIMIT, LIMIT);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.count(middle) > index {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+
+        // Events starting at the same time are in the order of their schedules
+        let skip = index - self.count(low - 1);
+        let timing = self
+            .timings
+            .iter()
+            .filter(|timing| timing.last(low) == low)
+            .nth(usize::try_from(skip).ok()?)?;
+        self.instance(timing.schedule, low)
+    }
+
+    /// The number of events from every schedule starting from UTC 00:00 until `time`
+    fn count(&self, time: i64) -> i64 {
+        self.timings.iter().fold(0i64, |count, timing| {
+            count.saturating_add(timing.count(time))
+        })
+    }
+
+    /// The instance of a schedule starting `start` minutes from UTC 00:00, if its start time can
+    /// be represented
+    fn instance(&self, schedule: usize, start: i64) -> Option<EventInstance> {
+        Some(EventInstance {
+            schedule: match &self.schedules {
+                Cow::Borrowed(schedules) => Cow::Borrowed(&schedules[schedule]),
+                Cow::Owned(schedules) => Cow::Owned(schedules[schedule].clone()),
+            },
+            start_time: Duration::try_minutes(start)?,
+        })
+    }
+
+    /// The first event to start after `time`, from UTC 00:00
+    pub fn next(&self, time: Duration) -> Option<EventInstance> {
+        self.occurrence(self.index_after(time))
+    }
+
+    /// The event active at `time`, from UTC 00:00
+    ///
+    /// If more than one event is active, the one whose schedule comes first is given.
+    pub fn now(&self, time: Duration) -> Option<EventInstance> {
+        let time = minutes(time);
+
+        // The last event of a schedule to start ends after every earlier one, so is active if any
+        // of them are
+        self.timings.iter().find_map(|timing| {
+            let start = timing.last(time);
+            let length = self.schedules[timing.schedule].length.num_minutes();
+            if time < start + length {
+                self.instance(timing.schedule, start)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// Whole minutes in `time`, rounded down so times before UTC 00:00 fall in the minute they're in
+fn minutes(time: Duration) -> i64 {
+    time.num_seconds().div_euclid(60)
+}
+
+/// The period of a table after adding `schedule` to one that repeats every `period` minutes, if
+/// it's short enough to list
+fn extend_period(period: i64, schedule: &EventSchedule) -> Option<i64> {
+    let frequency = schedule.frequency.num_minutes();
+    (period / gcd(period, frequency))
+        .checked_mul(frequency)
+        .filter(|&period| period <= MAX_PERIOD)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,73 @@
+//! The error given when building map metas and schedules that can't be iterated
+
+use core::fmt;
+
+use chrono::{Duration, NaiveTime};
+
+/// Why a [MapMeta](crate::meta::MapMeta) or [EventSchedule](crate::schedule::EventSchedule) is
+/// invalid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A map meta has no schedules
+    NoSchedules,
+
+    /// A schedule's frequency is not a positive whole number of minutes
+    InvalidFrequency {
+        name: &'static str,
+        frequency: Duration,
+    },
+
+    /// A schedule's length is not a positive whole number of minutes
+    InvalidLength {
+        name: &'static str,
+        length: Duration,
+    },
+
+    /// A schedule's events last longer than four weeks
+    LengthTooLong {
+        name: &'static str,
+        length: Duration,
+    },
+
+    /// A schedule's offset is not on a whole minute
+    InvalidOffset {
+        name: &'static str,
+        offset: NaiveTime,
+    },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSchedules => f.write_str("map meta has no schedules"),
+            Error::InvalidFrequency { name, frequency } => write!(
+                f,
+                "{} has a frequency of {}s, which is not a positive whole number of minutes",
+                name,
+                frequency.num_seconds()
+            ),
+            Error::InvalidLength { name, length } => write!(
+                f,
+                "{} has a length of {}s, which is not a positive whole number of minutes",
+                name,
+                length.num_seconds()
+            ),
+            Error::LengthTooLong { name, length } => write!(
+                f,
+                "{} has a length of {}s, which is longer than four weeks",
+                name,
+                length.num_seconds()
+            ),
+            Error::InvalidOffset { name, offset } => {
+                write!(
+                    f,
+                    "{} starts at {}, which is not on a whole minute",
+                    name, offset
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
@@ -1,21 +1,49 @@
-use std::fmt::Debug;
+use core::fmt::{self, Debug};
 
-use chrono::Duration;
+use alloc::borrow::Cow;
 
-use crate::schedule::EventSchedule;
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+
+use crate::{humanize::Humanizer, schedule::EventSchedule};
 
 /// A specific occurance of a map meta event
 #[derive(Clone)]
 pub struct EventInstance {
-    pub schedule: EventSchedule,
+    /// The event's schedule, borrowed from static tables for built-in map metas
+    pub schedule: Cow<'static, EventSchedule>,
     pub start_time: Duration,
 }
 
+impl EventInstance {
+    /// The time from UTC 00:00 the event ends
+    pub fn end_time(&self) -> Duration {
+        self.start_time + self.schedule.length
+    }
+
+    /// The moment the event starts, when iterating began on `date`
+    pub fn start_datetime(&self, date: NaiveDate) -> DateTime<Utc> {
+        date.and_time(NaiveTime::MIN).and_utc() + self.start_time
+    }
+
+    /// The moment the event ends, when iterating began on `date`
+    pub fn end_datetime(&self, date: NaiveDate) -> DateTime<Utc> {
+        date.and_time(NaiveTime::MIN).and_utc() + self.end_time()
+    }
+}
+
 impl Debug for EventInstance {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.schedule.fmt(f) {
             Err(e) => Err(e),
-            Ok(_) => write!(f, ", start: {}", self.start_time.num_minutes()),
+            Ok(_) => {
+                let sign = if self.start_time < Duration::zero() {
+                    "-"
+                } else {
+                    ""
+                };
+                let start = Humanizer::default().duration(self.start_time);
+                write!(f, ", start: {}{}", sign, start)
+            }
         }
     }
 }
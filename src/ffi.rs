@@ -0,0 +1,282 @@
+//! A C interface for using map meta schedules from other languages
+//!
+//! Only available with the `ffi` feature enabled. The header is at `include/gw2timers.h`, and is
+//! generated with `cbindgen --output include/gw2timers.h`.
+//!
+//! The crate is only built as a Rust library by default, so build the shared library with:
+//!
+//! ```sh
+//! cargo rustc --lib --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! Map metas are referred to by their index, from `0` up to [gw2timers_meta_count]. Times are Unix
+//! timestamps in seconds.
+//!
+//! # Ownership
+//!
+//! - Strings returned by this library are owned by it and live until the program exits. They must
+//!   not be freed or written to.
+//! - A [Gw2MetaIter] is owned by the caller once created with [gw2timers_meta_iter_new], and must
+//!   be freed with [gw2timers_meta_iter_free] exactly once.
+//! - Any `out` structs are owned by the caller and are only written to when a function returns
+//!   `true`.
+
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    os::raw::c_char,
+    ptr,
+    sync::{Mutex, OnceLock},
+};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Timelike, Utc};
+
+use crate::{clock::FixedClock, event::EventInstance, meta, meta::MapMetaKind};
+
+/// A single occurrence of an event
+#[repr(C)]
+pub struct Gw2Event {
+    /// The name of the event
+    pub name: *const c_char,
+
+    /// The name of the map meta the event is in
+    pub meta: *const c_char,
+
+    /// When the event starts
+    pub start: i64,
+
+    /// When the event ends
+    pub end: i64,
+}
+
+/// How often an event happens
+#[repr(C)]
+pub struct Gw2Schedule {
+    /// The name of the event
+    pub name: *const c_char,
+
+    /// The minutes after UTC 00:00 the event first starts
+    pub offset_minutes: i64,
+
+    /// The minutes between each start of the event
+    pub frequency_minutes: i64,
+
+    /// The minutes the event lasts
+    pub length_minutes: i64,
+}
+
+/// An iterator over the events in a map meta
+pub struct Gw2MetaIter {
+    kind: MapMetaKind,
+
+    /// The UTC day the iterator started on, which event times are counted from
+    date: NaiveDate,
+
+    iter: meta::IntoIter,
+}
+
+/// Get a NUL terminated copy of a name that lives until the program exits
+fn c_name(name: &'static str) -> *const c_char {
+    static NAMES: OnceLock<Mutex<HashMap<&'static str, CString>>> = OnceLock::new();
+    let mut names = NAMES
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap();
+    // The CString's buffer doesn't move when the map grows, and is never removed
+    names
+        .entry(name)
+        .or_insert_with(|| CString::new(name).unwrap())
+        .as_ptr()
+}
+
+fn kind(meta: usize) -> Option<MapMetaKind> {
+    MapMetaKind::all_keys().get(meta).copied()
+}
+
+fn datetime(timestamp: i64) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(timestamp, 0)
+}
+
+/// Write an event to `out`, returning whether there was an event to write
+///
+/// Events whose times are past the range of a [DateTime] aren't written.
+///
+/// # Safety
+///
+/// `out` must be null or point to a writable [Gw2Event].
+unsafe fn write_event(
+    out: *mut Gw2Event,
+    kind: MapMetaKind,
+    event: Option<EventInstance>,
+    date: NaiveDate,
+) -> bool {
+    let midnight = date.and_time(NaiveTime::MIN).and_utc();
+    let times = event.as_ref().and_then(|event| {
+        let start = midnight.checked_add_signed(event.start_time)?;
+        let end =
+            midnight.checked_add_signed(event.start_time.checked_add(&event.schedule.length)?)?;
+        Some((start, end))
+    });
+    match (out.as_mut(), event, times) {
+        (Some(out), Some(event), Some((start, end))) => {
+            *out = Gw2Event {
+                name: c_name(event.schedule.name),
+                meta: c_name(kind.info().name),
+                start: start.timestamp(),
+                end: end.timestamp(),
+            };
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The number of map metas
+#[no_mangle]
+pub extern "C" fn gw2timers_meta_count() -> usize {
+    MapMetaKind::all_keys().len()
+}
+
+/// The name of a map meta, or null if `meta` isn't a map meta
+#[no_mangle]
+pub extern "C" fn gw2timers_meta_name(meta: usize) -> *const c_char {
+    match kind(meta) {
+        Some(kind) => c_name(kind.info().name),
+        None => ptr::null(),
+    }
+}
+
+/// The name of a map meta's category, or null if `meta` isn't a map meta
+#[no_mangle]
+pub extern "C" fn gw2timers_meta_category(meta: usize) -> *const c_char {
+    match kind(meta) {
+        Some(kind) => c_name(kind.info().category.name()),
+        None => ptr::null(),
+    }
+}
+
+/// The number of event schedules in a map meta, or 0 if `meta` isn't a map meta
+#[no_mangle]
+pub extern "C" fn gw2timers_schedule_count(meta: usize) -> usize {
+    kind(meta).map_or(0, |kind| kind.info().schedules.len())
+}
+
+/// Write one of a map meta's event schedules to `out`
+///
+/// Returns false if `meta` or `schedule` is out of range.
+///
+/// # Safety
+///
+/// `out` must be null or point to a writable [Gw2Schedule].
+#[no_mangle]
+pub unsafe extern "C" fn gw2timers_schedule(
+    meta: usize,
+    schedule: usize,
+    out: *mut Gw2Schedule,
+) -> bool {
+    let schedules = match kind(meta) {
+        Some(kind) => kind.info().schedules,
+        None => return false,
+    };
+    match (out.as_mut(), schedules.get(schedule)) {
+        (Some(out), Some(schedule)) => {
+            *out = Gw2Schedule {
+                name: c_name(schedule.name),
+                offset_minutes: schedule.offset.num_seconds_from_midnight() as i64 / 60,
+                frequency_minutes: schedule.frequency.num_minutes(),
+                length_minutes: schedule.length.num_minutes(),
+            };
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Write the event active in a map meta at `now` to `out`
+///
+/// Returns false if there is no active event, `meta` isn't a map meta, or the event's times are
+/// past the range of dates this library handles.
+///
+/// # Safety
+///
+/// `out` must be null or point to a writable [Gw2Event].
+#[no_mangle]
+pub unsafe extern "C" fn gw2timers_active(meta: usize, now: i64, out: *mut Gw2Event) -> bool {
+    match (kind(meta), datetime(now)) {
+        (Some(kind), Some(now)) => {
+            let event = kind.iter_from(&FixedClock::new(now)).now();
+            write_event(out, kind, event, now.date_naive())
+        }
+        _ => false,
+    }
+}
+
+/// Write the next event to start in a map meta after `now` to `out`
+///
+/// Returns false if `meta` isn't a map meta, or the event's times are past the range of dates this
+/// library handles.
+///
+/// # Safety
+///
+/// `out` must be null or point to a writable [Gw2Event].
+#[no_mangle]
+pub unsafe extern "C" fn gw2timers_next(meta: usize, now: i64, out: *mut Gw2Event) -> bool {
+    match (kind(meta), datetime(now)) {
+        (Some(kind), Some(now)) => {
+            let event = kind.iter_from(&FixedClock::new(now)).next();
+            write_event(out, kind, event, now.date_naive())
+        }
+        _ => false,
+    }
+}
+
+/// Create an iterator over the events in a map meta that start after `now`
+///
+/// Returns null if `meta` isn't a map meta. The iterator must be freed with
+/// [gw2timers_meta_iter_free].
+#[no_mangle]
+pub extern "C" fn gw2timers_meta_iter_new(meta: usize, now: i64) -> *mut Gw2MetaIter {
+    match (kind(meta), datetime(now)) {
+        (Some(kind), Some(now)) => Box::into_raw(Box::new(Gw2MetaIter {
+            kind,
+            date: now.date_naive(),
+            iter: kind.iter_from(&FixedClock::new(now)),
+        })),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Write the iterator's next event to `out`
+///
+/// Returns false if `iter` is null, or the event's times are past the range of dates this library
+/// handles.
+///
+/// # Safety
+///
+/// `iter` must be null or an iterator from [gw2timers_meta_iter_new] that hasn't been freed, and
+/// `out` must be null or point to a writable [Gw2Event].
+#[no_mangle]
+pub unsafe extern "C" fn gw2timers_meta_iter_next(
+    iter: *mut Gw2MetaIter,
+    out: *mut Gw2Event,
+) -> bool {
+    match iter.as_mut() {
+        Some(iter) => {
+            let event = iter.iter.next();
+            write_event(out, iter.kind, event, iter.date)
+        }
+        None => false,
+    }
+}
+
+/// Free an iterator
+///
+/// # Safety
+///
+/// `iter` must be null or an iterator from [gw2timers_meta_iter_new] that hasn't been freed.
+#[no_mangle]
+pub unsafe extern "C" fn gw2timers_meta_iter_free(iter: *mut Gw2MetaIter) {
+    if !iter.is_null() {
+        drop(Box::from_raw(iter));
+    }
+}
@@ -0,0 +1,314 @@
+//! Formats times and lengths of time as text for people to read
+//!
+//! A [Humanizer] writes lengths of time like `1h 05m`, countdowns like `in 12m`,
+//! `started 3m ago` or `ends in 1h 05m`, and times of day in a chosen time zone. Text is written
+//! in any of the languages the game supports, in a [Style] that is either compact or verbose.
+
+use core::{fmt, str::FromStr};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+
+use crate::event::EventInstance;
+
+/// A language text can be written in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    /// Every supported language
+    pub fn all_keys() -> [Language; 4] {
+        [
+            Language::English,
+            Language::German,
+            Language::French,
+            Language::Spanish,
+        ]
+    }
+
+    /// The language's two letter code, e.g. `en`, as used by the game's API
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+            Language::French => "fr",
+            Language::Spanish => "es",
+        }
+    }
+
+    fn words(&self) -> &'static Words {
+        match self {
+            Language::English => &ENGLISH,
+            Language::German => &GERMAN,
+            Language::French => &FRENCH,
+            Language::Spanish => &SPANISH,
+        }
+    }
+}
+
+impl FromStr for Language {
+    type Err = ParseLanguageError;
+
+    /// Find a language by its two letter code, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Language::all_keys()
+            .iter()
+            .copied()
+            .find(|language| language.code().eq_ignore_ascii_case(s.trim()))
+            .ok_or(ParseLanguageError)
+    }
+}
+
+/// The error given when parsing an unknown [Language]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseLanguageError;
+
+impl fmt::Display for ParseLanguageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown language")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseLanguageError {}
+
+/// How much to abbreviate text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    /// Short units, e.g. `1h 05m` and `14:05`
+    #[default]
+    Compact,
+
+    /// Units spelled out, e.g. `1 hour and 5 minutes` and `14:05 UTC`
+    Verbose,
+}
+
+/// The words of a language, with `{}` replaced by a length of time in phrases
+struct Words {
+    /// Compact lengths under an hour, with `{m}` replaced by the minutes
+    minutes: &'static str,
+
+    /// Compact lengths of an hour or more, with `{h}` replaced by the hours and `{mm}` by the
+    /// minutes padded to two digits
+    hours: &'static str,
+
+    minute: &'static str,
+    minutes_plural: &'static str,
+    hour: &'static str,
+    hours_plural: &'static str,
+    and: &'static str,
+
+    /// Whether zero takes the singular, as in French
+    zero_singular: bool,
+
+    starts_in: &'static str,
+    started_ago: &'static str,
+    starts_now: &'static str,
+    ends_in: &'static str,
+    ended_ago: &'static str,
+    ends_now: &'static str,
+
+    /// What comes after what's active, e.g. in a summary of a map meta
+    next: &'static str,
+}
+
+const ENGLISH: Words = Words {
+    minutes: "{m}m",
+    hours: "{h}h {mm}m",
+    minute: "minute",
+    minutes_plural: "minutes",
+    hour: "hour",
+    hours_plural: "hours",
+    and: "and",
+    zero_singular: false,
+    starts_in: "in {}",
+    started_ago: "started {} ago",
+    starts_now: "starts now",
+    ends_in: "ends in {}",
+    ended_ago: "ended {} ago",
+    ends_now: "ends now",
+    next: "next {}",
+};
+
+const GERMAN: Words = Words {
+    minutes: "{m} Min.",
+    hours: "{h} Std. {mm} Min.",
+    minute: "Minute",
+    minutes_plural: "Minuten",
+    hour: "Stunde",
+    hours_plural: "Stunden",
+    and: "und",
+    zero_singular: false,
+    starts_in: "in {}",
+    started_ago: "begann vor {}",
+    starts_now: "beginnt jetzt",
+    ends_in: "endet in {}",
+    ended_ago: "endete vor {}",
+    ends_now: "endet jetzt",
+    next: "als Nächstes {}",
+};
+
+const FRENCH: Words = Words {
+    minutes: "{m} min",
+    hours: "{h} h {mm} min",
+    minute: "minute",
+    minutes_plural: "minutes",
+    hour: "heure",
+    hours_plural: "heures",
+    and: "et",
+    zero_singular: true,
+    starts_in: "dans {}",
+    started_ago: "a commencé il y a {}",
+    starts_now: "commence maintenant",
+    ends_in: "se termine dans {}",
+    ended_ago: "s'est terminé il y a {}",
+    ends_now: "se termine maintenant",
+    next: "ensuite {}",
+};
+
+const SPANISH: Words = Words {
+    minutes: "{m} min",
+    hours: "{h} h {mm} min",
+    minute: "minuto",
+    minutes_plural: "minutos",
+    hour: "hora",
+    hours_plural: "horas",
+    and: "y",
+    zero_singular: false,
+    starts_in: "en {}",
+    started_ago: "empezó hace {}",
+    starts_now: "empieza ahora",
+    ends_in: "termina en {}",
+    ended_ago: "terminó hace {}",
+    ends_now: "termina ahora",
+    next: "después {}",
+};
+
+/// Writes times and lengths of time in a [Language] and [Style]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Humanizer {
+    pub language: Language,
+    pub style: Style,
+}
+
+impl Humanizer {
+    /// Create a humanizer writing compact text in `language`
+    pub fn new(language: Language) -> Self {
+        Humanizer {
+            language,
+            style: Style::Compact,
+        }
+    }
+
+    /// Set the style text is written in
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Write a length of time in whole minutes, e.g. `1h 05m` or `1 hour and 5 minutes`
+    ///
+    /// Negative lengths are written the same as positive ones.
+    pub fn duration(&self, duration: Duration) -> String {
+        let words = self.language.words();
+        let minutes = duration.num_minutes().abs();
+        let (hours, minutes) = (minutes / 60, minutes % 60);
+        match self.style {
+            Style::Compact if hours == 0 => words.minutes.replace("{m}", &minutes.to_string()),
+            Style::Compact => words
+                .hours
+                .replace("{h}", &hours.to_string())
+                .replace("{mm}", &format!("{:02}", minutes)),
+            Style::Verbose => {
+                let minutes_text = count(words, minutes, words.minute, words.minutes_plural);
+                if hours == 0 {
+                    return minutes_text;
+                }
+                let hours_text = count(words, hours, words.hour, words.hours_plural);
+                if minutes == 0 {
+                    hours_text
+                } else {
+                    format!("{} {} {}", hours_text, words.and, minutes_text)
+                }
+            }
+        }
+    }
+
+    /// Write how long until something starts, e.g. `in 12m`, or how long ago it started, e.g.
+    /// `started 3m ago`
+    pub fn starts(&self, until: Duration) -> String {
+        let words = self.language.words();
+        self.relative(until, words.starts_in, words.started_ago, words.starts_now)
+    }
+
+    /// Write how long until something ends, e.g. `ends in 1h 05m`, or how long ago it ended
+    pub fn ends(&self, until: Duration) -> String {
+        let words = self.language.words();
+        self.relative(until, words.ends_in, words.ended_ago, words.ends_now)
+    }
+
+    /// Write that `name` comes next, e.g. `next Fire Elemental`
+    pub fn next(&self, name: &str) -> String {
+        self.language.words().next.replace("{}", name)
+    }
+
+    /// Write a countdown for an event at `time`, from the same UTC 00:00 as the event's times
+    ///
+    /// This is how long until the event starts, or until it ends if it's active, or how long ago
+    /// it ended.
+    pub fn countdown(&self, event: &EventInstance, time: Duration) -> String {
+        if event.start_time > time {
+            self.starts(event.start_time - time)
+        } else {
+            self.ends(event.end_time() - time)
+        }
+    }
+
+    /// Write the time of day of `time` in `tz`, e.g. `14:05`, with the time zone when verbose
+    pub fn time<Tz: TimeZone>(&self, time: DateTime<Utc>, tz: &Tz) -> String
+    where
+        Tz::Offset: fmt::Display,
+    {
+        let time = time.with_timezone(tz);
+        match self.style {
+            Style::Compact => time.format("%H:%M").to_string(),
+            Style::Verbose => time.format("%H:%M %Z").to_string(),
+        }
+    }
+
+    fn relative(
+        &self,
+        until: Duration,
+        future: &'static str,
+        past: &'static str,
+        now: &'static str,
+    ) -> String {
+        if until.num_minutes() == 0 {
+            now.to_string()
+        } else if until < Duration::zero() {
+            past.replace("{}", &self.duration(until))
+        } else {
+            future.replace("{}", &self.duration(until))
+        }
+    }
+}
+
+/// Write a number with the singular or plural of its unit
+fn count(words: &Words, number: i64, singular: &str, plural: &str) -> String {
+    let unit = if number == 1 || (number == 0 && words.zero_singular) {
+        singular
+    } else {
+        plural
+    };
+    format!("{} {}", number, unit)
+}
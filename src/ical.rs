@@ -0,0 +1,241 @@
+//! Exports [EventSchedule]s as an iCalendar ([RFC 5545](https://www.rfc-editor.org/rfc/rfc5545))
+//! file
+
+use core::fmt::{self, Display, Write};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+
+use crate::{clock::Clock, meta::MapMeta, schedule::EventSchedule};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// An iCalendar file of map meta events
+///
+/// Schedules that line up with days are written as a single recurring event. Any other schedule
+/// has each occurrence written out for the number of days set with [Calendar::days].
+pub struct Calendar {
+    /// The day the calendar starts on
+    date: NaiveDate,
+
+    /// When the calendar was created, for each event's `DTSTAMP`
+    created: DateTime<Utc>,
+
+    /// How many days of events to write out for schedules that can't recur
+    days: i64,
+
+    /// How long before each event to give a reminder
+    alarms: Vec<Duration>,
+
+    /// The schedules in the calendar, with the name of the meta they belong to
+    schedules: Vec<(Option<&'static str>, EventSchedule)>,
+}
+
+impl Calendar {
+    /// Create an empty calendar starting on `date`, stamped with the time from `clock`
+    pub fn new(date: NaiveDate, clock: &impl Clock) -> Self {
+        Calendar {
+            date,
+            created: clock.now(),
+            days: 7,
+            alarms: Vec::new(),
+            schedules: Vec::new(),
+        }
+    }
+
+    /// Set how many days of events to write out for schedules that can't recur
+    pub fn days(mut self, days: u32) -> Self {
+        self.days = days as i64;
+        self
+    }
+
+    /// Add a reminder to every event, some amount of time before it starts, or after it starts if
+    /// `lead_time` is negative
+    pub fn alarm(mut self, lead_time: Duration) -> Self {
+        self.alarms.push(lead_time);
+        self
+    }
+
+    /// Add every event schedule in a map meta
+    pub fn meta(mut self, meta: MapMeta) -> Self {
+        let name = meta.name;
+        self.schedules.extend(
+            meta.schedules
+                .iter()
+                .map(|schedule| (Some(name), schedule.clone())),
+        );
+        self
+    }
+
+    /// Add a single event schedule
+    pub fn schedule(mut self, schedule: EventSchedule) -> Self {
+        self.schedules.push((None, schedule));
+        self
+    }
+
+    fn write_event(
+        &self,
+        out: &mut Lines,
+        meta: Option<&str>,
+        schedule: &EventSchedule,
+        start: Duration,
+        rule: Option<String>,
+    ) {
+        let slug = |name: &str| -> String {
+            name.chars()
+                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect::<String>()
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join("-")
+        };
+        let offset = schedule.offset.num_seconds_from_midnight() / 60;
+        let mut uid = format!(
+            "{}-{}-{:04}-{}",
+            slug(meta.unwrap_or("event")),
+            slug(schedule.name),
+            offset,
+            schedule.frequency.num_minutes()
+        );
+        if rule.is_none() {
+            // Each written out occurrence needs its own UID
+            uid = format!("{}-{}", uid, date_time(self.date, start));
+        }
+        let summary = match meta {
+            Some(meta) => format!("{} ({})", schedule.name, meta),
+            None => schedule.name.to_string(),
+        };
+
+        out.line("BEGIN:VEVENT");
+        out.line(&format!("UID:{}@gw2timers", uid));
+        out.line(&format!(
+            "DTSTAMP:{}",
+            self.created.format("%Y%m%dT%H%M%SZ")
+        ));
+        out.line(&format!("DTSTART:{}", date_time(self.date, start)));
+        out.line(&format!("DURATION:{}", duration(schedule.length)));
+        if let Some(rule) = rule {
+            out.line(&format!("RRULE:{}", rule));
+        }
+        out.line(&format!("SUMMARY:{}", escape(&summary)));
+        if let Some(meta) = meta {
+            out.line(&format!("CATEGORIES:{}", escape(meta)));
+        }
+        for lead_time in &self.alarms {
+            out.line("BEGIN:VALARM");
+            out.line("ACTION:DISPLAY");
+            out.line(&format!("DESCRIPTION:{}", escape(&summary)));
+            out.line(&format!("TRIGGER:{}", trigger(*lead_time)));
+            out.line("END:VALARM");
+        }
+        out.line("END:VEVENT");
+    }
+}
+
+impl Display for Calendar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = Lines(String::new());
+        out.line("BEGIN:VCALENDAR");
+        out.line("VERSION:2.0");
+        out.line("PRODID:-//purplg//gw2timers//EN");
+        out.line("CALSCALE:GREGORIAN");
+
+        for (meta, schedule) in &self.schedules {
+            let frequency = schedule.frequency.num_minutes();
+            let first = schedule.iter().fast_forward(Duration::minutes(-1)).next();
+            let rule = match frequency {
+                _ if schedule.frequency != Duration::minutes(frequency) => None,
+                f if f > 0 && f % MINUTES_PER_DAY == 0 => {
+                    Some(format!("FREQ=DAILY;INTERVAL={}", f / MINUTES_PER_DAY))
+                }
+                f if f > 0 && MINUTES_PER_DAY % f == 0 && f % 60 == 0 => {
+                    Some(format!("FREQ=HOURLY;INTERVAL={}", f / 60))
+                }
+                f if f > 0 && MINUTES_PER_DAY % f == 0 => {
+                    Some(format!("FREQ=MINUTELY;INTERVAL={}", f))
+                }
+                _ => None,
+            };
+
+            match (rule, first) {
+                (Some(rule), Some(first)) => {
+                    self.write_event(&mut out, *meta, schedule, first.start_time, Some(rule))
+                }
+                _ => schedule
+                    .iter()
+                    .fast_forward(Duration::minutes(-1))
+                    .take_while(|event| event.start_time < Duration::days(self.days))
+                    .for_each(|event| {
+                        self.write_event(&mut out, *meta, schedule, event.start_time, None)
+                    }),
+            }
+        }
+
+        out.line("END:VCALENDAR");
+        f.write_str(&out.0)
+    }
+}
+
+/// Content lines, folded to 75 octets and ended with CRLF
+struct Lines(String);
+
+impl Lines {
+    fn line(&mut self, line: &str) {
+        let mut length = 0;
+        for c in line.chars() {
+            if length + c.len_utf8() > 75 {
+                self.0.push_str("\r\n ");
+                length = 1;
+            }
+            self.0.push(c);
+            length += c.len_utf8();
+        }
+        self.0.push_str("\r\n");
+    }
+}
+
+/// Format a UTC date-time `time` after the start of `date`
+fn date_time(date: NaiveDate, time: Duration) -> String {
+    (date.and_time(NaiveTime::MIN) + time)
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Format a duration, like `PT1H30M`
+fn duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+    let mut out = String::from("PT");
+    if seconds >= 3600 {
+        write!(out, "{}H", seconds / 3600).unwrap();
+    }
+    if seconds % 3600 >= 60 {
+        write!(out, "{}M", seconds % 3600 / 60).unwrap();
+    }
+    if seconds % 60 > 0 || seconds == 0 {
+        write!(out, "{}S", seconds % 60).unwrap();
+    }
+    out
+}
+
+/// Format when an alarm goes off relative to the start of its event, like `-PT10M`
+fn trigger(lead_time: Duration) -> String {
+    if lead_time < Duration::zero() {
+        duration(-lead_time)
+    } else {
+        format!("-{}", duration(lead_time))
+    }
+}
+
+/// Escape text values
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
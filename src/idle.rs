@@ -0,0 +1,139 @@
+//! Finds the times a map is free of its meta events
+//!
+//! An [IdleFinder] gives every [Interval] within a window of time when none of a set of events is
+//! active, e.g. to do hearts or gather in Auric Basin while the pylons aren't up:
+//!
+//! ```
+//! use chrono::Duration;
+//! use gw2timers::{idle::IdleFinder, meta::MapMetaKind};
+//!
+//! let quiet = IdleFinder::events(MapMetaKind::AuricBasin, &["Pylons"]);
+//! for interval in quiet.between(Duration::zero(), Duration::days(1)) {
+//!     println!("free for {}m", interval.length().num_minutes());
+//! }
+//! ```
+
+use alloc::{borrow::Cow, vec::Vec};
+
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+
+use crate::{cursor::Cursor, cycle::CycleTable, meta::MapMetaKind, schedule::EventSchedule};
+
+/// A span of time, from UTC 00:00 of the day iterating began
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Interval {
+    pub start: Duration,
+    pub end: Duration,
+}
+
+impl Interval {
+    /// How long the interval lasts
+    pub fn length(&self) -> Duration {
+        self.end - self.start
+    }
+
+    /// The moment the interval starts, when iterating began on `date`
+    pub fn start_datetime(&self, date: NaiveDate) -> DateTime<Utc> {
+        date.and_time(NaiveTime::MIN).and_utc() + self.start
+    }
+
+    /// The moment the interval ends, when iterating began on `date`
+    pub fn end_datetime(&self, date: NaiveDate) -> DateTime<Utc> {
+        date.and_time(NaiveTime::MIN).and_utc() + self.end
+    }
+}
+
+/// Finds the intervals when none of a set of events is active
+pub struct IdleFinder {
+    table: Cow<'static, CycleTable>,
+}
+
+impl IdleFinder {
+    /// Find when none of `schedules` is active
+    pub fn new(schedules: impl Into<Cow<'static, [EventSchedule]>>) -> Self {
+        IdleFinder {
+            table: Cow::Owned(CycleTable::new(schedules)),
+        }
+    }
+
+    /// Find the free time between the events of a map meta
+    pub fn meta(kind: MapMetaKind) -> Self {
+        IdleFinder {
+            table: kind.cycle_table(),
+        }
+    }
+
+    /// Find when none of the events of a map meta named in `names` is active
+    pub fn events(kind: MapMetaKind, names: &[&str]) -> Self {
+        IdleFinder::new(
+            kind.info()
+                .schedules
+                .iter()
+                .filter(|schedule| names.contains(&schedule.name))
+                .cloned()
+                .collect::<Vec<EventSchedule>>(),
+        )
+    }
+
+    /// Iterate over the free intervals from `start` to `end`, from UTC 00:00
+    ///
+    /// Intervals are cut off at the edges of the window, so the first may start at `start` and
+    /// the last may end at `end`.
+    pub fn between(&self, start: Duration, end: Duration) -> Intervals {
+        // Events that started this long before the window may still be active in it
+        let mut events = Cursor::new(self.table.clone());
+        events.seek(start - self.table.longest());
+        Intervals {
+            events,
+            free_from: start,
+            end,
+        }
+    }
+}
+
+/// An iterator that gives each free [Interval] from an [IdleFinder] in order
+pub struct Intervals {
+    events: Cursor,
+
+    /// The earliest time no event seen so far is active
+    free_from: Duration,
+
+    /// The end of the window
+    end: Duration,
+}
+
+impl Iterator for Intervals {
+    type Item = Interval;
+
+    fn next(&mut self) -> Option<Interval> {
+        while self.free_from < self.end {
+            let event = match self.events.peek() {
+                Some(event) if event.start_time < self.end => event,
+                _ => {
+                    // Free until the end of the window
+                    let interval = Interval {
+                        start: self.free_from,
+                        end: self.end,
+                    };
+                    self.free_from = self.end;
+                    return Some(interval);
+                }
+            };
+            self.events.next();
+            if event.end_time() <= event.start_time {
+                // Never active, so it doesn't split the free time around it
+                continue;
+            }
+
+            let interval = Interval {
+                start: self.free_from,
+                end: event.start_time,
+            };
+            self.free_from = self.free_from.max(event.end_time());
+            if interval.start < interval.end {
+                return Some(interval);
+            }
+        }
+        None
+    }
+}
@@ -1,20 +1,253 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod agenda;
+pub mod analytics;
 pub mod category;
+pub mod chat;
+pub mod clock;
+pub mod conflict;
+pub mod cursor;
+pub mod cycle;
+pub mod error;
 pub mod event;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod humanize;
+pub mod ical;
+pub mod idle;
 pub mod meta;
+#[cfg(feature = "notify")]
+pub mod notify;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod reminder;
+pub mod route;
 pub mod schedule;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "tokio")]
+pub mod stream;
+pub mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(test)]
+mod agenda_tests {
+    use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
+
+    use crate::{agenda::Agenda, meta::MapMetaKind};
+
+    #[test]
+    fn test_carried_over() {
+        // Night runs from 23:45 until 00:25
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let agenda = Agenda::for_metas(date, &Utc, &[MapMetaKind::DayAndNight]);
+        let first = &agenda.entries[0];
+        assert_eq!(first.event.schedule.name, "Night");
+        assert!(first.carried_over);
+        assert_eq!(
+            first.start,
+            Utc.with_ymd_and_hms(2022, 2, 28, 23, 45, 0).unwrap()
+        );
+        assert_eq!(
+            first.end,
+            Utc.with_ymd_and_hms(2022, 3, 1, 0, 25, 0).unwrap()
+        );
+
+        // Four events every two hours, and the night carried over
+        assert_eq!(agenda.entries.len(), 49);
+        assert!(agenda.entries[1..].iter().all(|entry| !entry.carried_over));
+        let last = agenda.entries.last().unwrap();
+        assert_eq!(last.event.schedule.name, "Night");
+        assert_eq!(
+            last.start,
+            Utc.with_ymd_and_hms(2022, 3, 1, 23, 45, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_time_zone() {
+        // The day starts at 22:00 UTC the day before
+        let tz = FixedOffset::east_opt(2 * 60 * 60).unwrap();
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let agenda = Agenda::for_metas(date, &tz, &[MapMetaKind::DayAndNight]);
+        assert_eq!(
+            agenda.base_date,
+            NaiveDate::from_ymd_opt(2022, 2, 28).unwrap()
+        );
+
+        let first = &agenda.entries[0];
+        assert_eq!(first.event.schedule.name, "Night");
+        assert!(first.carried_over);
+        assert_eq!(
+            first.start,
+            tz.with_ymd_and_hms(2022, 2, 28, 23, 45, 0).unwrap()
+        );
+        assert_eq!(
+            first.end,
+            tz.with_ymd_and_hms(2022, 3, 1, 0, 25, 0).unwrap()
+        );
+        assert_eq!(agenda.entries.len(), 49);
+    }
+
+    #[test]
+    fn test_nothing_carried_over() {
+        // No world boss runs over midnight, and one spawns every hour except three
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let agenda = Agenda::for_metas(date, &Utc, &[MapMetaKind::HardWorldBosses]);
+        assert!(agenda.entries.iter().all(|entry| !entry.carried_over));
+        assert!(agenda
+            .entries
+            .iter()
+            .all(|entry| entry.start.date_naive() == date));
+        assert_eq!(agenda.entries.len(), 18);
+    }
+
+    #[test]
+    fn test_by_meta() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let agenda = Agenda::for_metas(
+            date,
+            &Utc,
+            &[MapMetaKind::WorldBosses, MapMetaKind::DayAndNight],
+        );
+        let groups = agenda.by_meta();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, MapMetaKind::DayAndNight);
+        assert_eq!(groups[0].1.len(), 49);
+        assert_eq!(groups[1].0, MapMetaKind::WorldBosses);
+        assert!(groups[1]
+            .1
+            .iter()
+            .all(|entry| entry.meta == MapMetaKind::WorldBosses));
+
+        // Chronological across metas
+        assert!(agenda
+            .entries
+            .windows(2)
+            .all(|pair| pair[0].start <= pair[1].start));
+    }
+
+    #[test]
+    fn test_all_metas() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let agenda = Agenda::new(date, &Utc);
+        assert_eq!(agenda.by_meta().len(), MapMetaKind::all_keys().len());
+    }
+
+    #[test]
+    fn test_daylight_saving() {
+        // Clocks in Santiago skip from 00:00 to 01:00, so the day starts at 04:00 UTC
+        let tz = chrono_tz::America::Santiago;
+        let date = NaiveDate::from_ymd_opt(2022, 9, 11).unwrap();
+        let agenda = Agenda::for_metas(date, &tz, &[MapMetaKind::DayAndNight]);
+        let first = &agenda.entries[0];
+        assert_eq!(first.event.schedule.name, "Night");
+        assert!(first.carried_over);
+        assert_eq!(
+            first.start.with_timezone(&Utc),
+            Utc.with_ymd_and_hms(2022, 9, 11, 3, 45, 0).unwrap()
+        );
+
+        // The day is only 23 hours long
+        let last = agenda.entries.last().unwrap();
+        assert!(
+            last.start.with_timezone(&Utc) < Utc.with_ymd_and_hms(2022, 9, 12, 3, 0, 0).unwrap()
+        );
+        assert_eq!(agenda.entries.len(), 47);
+    }
+}
+
+#[cfg(test)]
+mod analytics_tests {
+    use chrono::Duration;
+
+    use crate::{
+        analytics::{Analytics, Span},
+        meta::MapMetaKind,
+    };
+
+    #[test]
+    fn test_events() {
+        let analytics = Analytics::new(&[MapMetaKind::DayAndNight, MapMetaKind::DragonsEnd]);
+        let stats = analytics.events(Span::Day);
+
+        // Night runs for 40 minutes every two hours, including the one carried over from 23:45
+        let night = stats.iter().find(|stats| stats.name == "Night").unwrap();
+        assert_eq!(night.meta, MapMetaKind::DayAndNight);
+        assert_eq!(night.occurrences, 12);
+        assert_eq!(night.active, Duration::hours(8));
+        assert!((night.duty_cycle - 1.0 / 3.0).abs() < 1e-9);
+
+        // Both schedules of Preparations are counted together
+        let preparations = stats
+            .iter()
+            .filter(|stats| stats.name == "Preparations")
+            .collect::<Vec<_>>();
+        assert_eq!(preparations.len(), 1);
+        assert_eq!(preparations[0].occurrences, 24);
+
+        let day_and_night = stats
+            .iter()
+            .filter(|stats| stats.meta == MapMetaKind::DayAndNight);
+        assert!((day_and_night.map(|stats| stats.duty_cycle).sum::<f64>() - 1.0).abs() < 1e-9);
+
+        let week = analytics.events(Span::Week);
+        let night = week.iter().find(|stats| stats.name == "Night").unwrap();
+        assert_eq!(night.occurrences, 7 * 12);
+        assert_eq!(night.active, Duration::hours(7 * 8));
+    }
+
+    #[test]
+    fn test_heatmap() {
+        // Day and night always has exactly one event active
+        let heatmap = Analytics::new(&[MapMetaKind::DayAndNight]).heatmap(Span::Day);
+        assert_eq!(heatmap.hours.len(), 24);
+        assert!(heatmap
+            .hours
+            .iter()
+            .all(|hour| hour.peak == 1 && hour.active == Duration::hours(1)));
+        assert_eq!(heatmap.busiest(), Some(0));
+        assert_eq!(heatmap.quietest(), Some(0));
+
+        // Eighteen hard world bosses a day, each active for 30 minutes and never at once
+        let heatmap = Analytics::new(&[MapMetaKind::HardWorldBosses]).heatmap(Span::Day);
+        let total = heatmap
+            .hours
+            .iter()
+            .fold(Duration::zero(), |total, hour| total + hour.active);
+        assert_eq!(total, Duration::minutes(18 * 30));
+        assert!(heatmap.hours.iter().all(|hour| hour.peak <= 1));
+        assert_eq!(heatmap.hours[0].average(), 0.5);
+    }
+
+    #[test]
+    fn test_week_repeats_daily() {
+        let analytics = Analytics::all();
+        let day = analytics.heatmap(Span::Day);
+        let week = analytics.heatmap(Span::Week);
+        assert_eq!(week.hours.len(), 7 * 24);
+        for hours in week.hours.chunks(24) {
+            assert_eq!(hours, &day.hours[..]);
+        }
+        assert!(day.hours.iter().all(|hour| hour.peak > 0));
+    }
+}
 
 #[cfg(test)]
 mod event_tests {
-    use chrono::{Duration, NaiveTime};
+    use chrono::{Duration, NaiveTime, Timelike};
 
-    use crate::schedule::EventSchedule;
+    use crate::{meta::MapMetaKind, schedule::EventSchedule};
 
     #[test]
     #[rustfmt::skip]
     fn test_event_iter() {
         let test_event_schedule = EventSchedule {
             name: "Reoccurring event",
-            offset: NaiveTime::from_hms(0, 20, 0),
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
             frequency: Duration::hours(1),
             length: Duration::minutes(15),
         };
@@ -26,7 +259,7 @@ mod event_tests {
         assert_eq!(event_iter.next().unwrap().start_time.num_minutes(), 140);
         assert_eq!(event_iter.next().unwrap().start_time.num_minutes(), 200);
 
-        event_iter = event_iter.time(NaiveTime::from_hms(3, 0, 0));
+        event_iter = event_iter.time(NaiveTime::from_hms_opt(3, 0, 0).unwrap());
         assert_eq!(event_iter.next().unwrap().start_time.num_minutes(), 200);
 
         let mut event_iter = test_event_schedule.iter().fast_forward(Duration::minutes(0));
@@ -45,59 +278,481 @@ mod event_tests {
     fn test_event_schedule_now() {
         let test_event_schedule = EventSchedule {
             name: "Reoccurring event",
-            offset: NaiveTime::from_hms(1, 45, 0),
+            offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
             frequency: Duration::hours(3),
             length: Duration::minutes(30),
         };
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(1, 44, 0))
+            .time(NaiveTime::from_hms_opt(1, 44, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(1, 45, 0))
+            .time(NaiveTime::from_hms_opt(1, 45, 0).unwrap())
             .now();
         assert!(now.is_some());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(2, 14, 0))
+            .time(NaiveTime::from_hms_opt(2, 14, 0).unwrap())
             .now();
         assert!(now.is_some());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(2, 15, 0))
+            .time(NaiveTime::from_hms_opt(2, 15, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(4, 44, 0))
+            .time(NaiveTime::from_hms_opt(4, 44, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(4, 45, 0))
+            .time(NaiveTime::from_hms_opt(4, 45, 0).unwrap())
             .now();
         assert!(now.is_some());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(5, 14, 0))
+            .time(NaiveTime::from_hms_opt(5, 14, 0).unwrap())
             .now();
         assert!(now.is_some());
 
         let now = test_event_schedule
             .iter()
-            .time(NaiveTime::from_hms(5, 15, 0))
+            .time(NaiveTime::from_hms_opt(5, 15, 0).unwrap())
             .now();
         assert!(now.is_none());
     }
+
+    #[test]
+    fn test_now_across_cycle_boundary() {
+        // Night starts at 01:45 and lasts 40 minutes, so it is still active at 00:10
+        let night = MapMetaKind::DayAndNight.info().schedules[3].clone();
+        let now = night
+            .iter()
+            .time(NaiveTime::from_hms_opt(0, 10, 0).unwrap())
+            .now()
+            .unwrap();
+        assert_eq!(now.schedule.name, "Night");
+        assert_eq!(now.start_time, Duration::minutes(-15));
+        assert_eq!(now.end_time(), Duration::minutes(25));
+
+        let now = MapMetaKind::DayAndNight
+            .into_iter()
+            .time(NaiveTime::from_hms_opt(0, 10, 0).unwrap())
+            .now()
+            .unwrap();
+        assert_eq!(now.schedule.name, "Night");
+        assert_eq!(now.start_time, Duration::minutes(-15));
+    }
+
+    #[test]
+    fn test_built_in_events_across_cycle_boundary() {
+        let mut wrapping = Vec::new();
+        for kind in MapMetaKind::all_keys() {
+            for schedule in kind.info().schedules.iter() {
+                let frequency = schedule.frequency.num_minutes();
+                let offset = schedule.offset.num_seconds_from_midnight() as i64 / 60 % frequency;
+                let end = offset + schedule.length.num_minutes();
+                if end <= frequency {
+                    continue;
+                }
+                wrapping.push(schedule.name);
+
+                // Every minute of the wrapped part of each cycle, over two days
+                for cycle in 0..2 * 24 * 60 / frequency {
+                    for minute in 0..end - frequency {
+                        let time = Duration::minutes(cycle * frequency + minute);
+                        let now = schedule
+                            .iter()
+                            .fast_forward(time)
+                            .now()
+                            .unwrap_or_else(|| panic!("{} at {}", schedule.name, time));
+                        assert_eq!(
+                            now.start_time,
+                            Duration::minutes((cycle - 1) * frequency + offset)
+                        );
+                        assert!(kind.into_iter().fast_forward(time).now().is_some());
+                    }
+                }
+            }
+        }
+        assert!(wrapping.contains(&"Night"));
+        assert!(wrapping.contains(&"Night: Night and the Enemy"));
+    }
+}
+
+#[cfg(all(test, feature = "ffi"))]
+mod ffi_tests {
+    use std::{ffi::CStr, os::raw::c_char, ptr};
+
+    use chrono::{DateTime, Utc};
+
+    use crate::ffi::*;
+
+    /// 2022-03-01 08:41 UTC
+    const NOW: i64 = 1646124060;
+
+    fn string(name: *const c_char) -> &'static str {
+        unsafe { CStr::from_ptr(name) }.to_str().unwrap()
+    }
+
+    fn empty_event() -> Gw2Event {
+        Gw2Event {
+            name: ptr::null(),
+            meta: ptr::null(),
+            start: 0,
+            end: 0,
+        }
+    }
+
+    #[test]
+    fn test_metas() {
+        assert_eq!(gw2timers_meta_count(), 28);
+        assert_eq!(string(gw2timers_meta_name(1)), "World Bosses");
+        assert_eq!(string(gw2timers_meta_category(1)), "Core Tyria");
+        assert_eq!(gw2timers_meta_name(1), gw2timers_meta_name(1));
+        assert!(gw2timers_meta_name(28).is_null());
+
+        assert_eq!(gw2timers_schedule_count(0), 4);
+        let mut schedule = Gw2Schedule {
+            name: ptr::null(),
+            offset_minutes: 0,
+            frequency_minutes: 0,
+            length_minutes: 0,
+        };
+        assert!(unsafe { gw2timers_schedule(0, 2, &mut schedule) });
+        assert_eq!(string(schedule.name), "Dusk");
+        assert_eq!(schedule.offset_minutes, 100);
+        assert_eq!(schedule.frequency_minutes, 120);
+        assert_eq!(schedule.length_minutes, 5);
+        assert!(!unsafe { gw2timers_schedule(0, 4, &mut schedule) });
+        assert!(!unsafe { gw2timers_schedule(0, 0, ptr::null_mut()) });
+    }
+
+    #[test]
+    fn test_queries() {
+        let mut event = empty_event();
+        assert!(unsafe { gw2timers_active(1, NOW, &mut event) });
+        assert_eq!(string(event.name), "Claw of Jormag");
+        assert_eq!(string(event.meta), "World Bosses");
+        assert_eq!(event.end, NOW + 4 * 60);
+
+        assert!(unsafe { gw2timers_next(1, NOW, &mut event) });
+        assert_eq!(string(event.name), "Fire Elemental");
+        assert_eq!(event.start, NOW + 4 * 60);
+        assert!(!unsafe { gw2timers_next(28, NOW, &mut event) });
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        // The last second of the latest date, so the next event is past the range of dates
+        let last = DateTime::<Utc>::MAX_UTC.timestamp();
+        let mut event = empty_event();
+        assert!(!unsafe { gw2timers_next(1, last, &mut event) });
+        assert!(!unsafe { gw2timers_active(1, last, &mut event) });
+        assert!(!unsafe { gw2timers_active(1, i64::MAX, &mut event) });
+        assert!(event.name.is_null());
+
+        let iter = gw2timers_meta_iter_new(1, last);
+        assert!(!unsafe { gw2timers_meta_iter_next(iter, &mut event) });
+        unsafe { gw2timers_meta_iter_free(iter) };
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut event = empty_event();
+        let iter = gw2timers_meta_iter_new(1, NOW);
+        let names = (0..3)
+            .map(|_| {
+                assert!(unsafe { gw2timers_meta_iter_next(iter, &mut event) });
+                string(event.name)
+            })
+            .collect::<Vec<&str>>();
+        unsafe { gw2timers_meta_iter_free(iter) };
+        assert_eq!(
+            names,
+            vec![
+                "Fire Elemental",
+                "Admiral Taidha Covington",
+                "Great Jungle Wurm"
+            ]
+        );
+
+        assert!(gw2timers_meta_iter_new(28, NOW).is_null());
+        assert!(!unsafe { gw2timers_meta_iter_next(ptr::null_mut(), &mut event) });
+        unsafe { gw2timers_meta_iter_free(ptr::null_mut()) };
+    }
+}
+
+#[cfg(test)]
+mod humanize_tests {
+    use alloc::borrow::Cow;
+
+    use chrono::{Duration, FixedOffset, NaiveTime, TimeZone, Utc};
+
+    use crate::{
+        event::EventInstance,
+        humanize::{Humanizer, Language, Style},
+        schedule::EventSchedule,
+    };
+
+    #[test]
+    fn test_duration() {
+        let compact = Humanizer::default();
+        assert_eq!(compact.duration(Duration::minutes(12)), "12m");
+        assert_eq!(compact.duration(Duration::minutes(65)), "1h 05m");
+        assert_eq!(compact.duration(Duration::minutes(-3)), "3m");
+        assert_eq!(compact.duration(Duration::seconds(59)), "0m");
+
+        let verbose = compact.style(Style::Verbose);
+        assert_eq!(verbose.duration(Duration::minutes(1)), "1 minute");
+        assert_eq!(
+            verbose.duration(Duration::minutes(65)),
+            "1 hour and 5 minutes"
+        );
+        assert_eq!(verbose.duration(Duration::hours(2)), "2 hours");
+        assert_eq!(verbose.duration(Duration::zero()), "0 minutes");
+    }
+
+    #[test]
+    fn test_relative() {
+        let english = Humanizer::new(Language::English);
+        assert_eq!(english.starts(Duration::minutes(12)), "in 12m");
+        assert_eq!(english.starts(Duration::minutes(-3)), "started 3m ago");
+        assert_eq!(english.starts(Duration::seconds(30)), "starts now");
+        assert_eq!(english.ends(Duration::minutes(65)), "ends in 1h 05m");
+        assert_eq!(english.ends(Duration::minutes(-3)), "ended 3m ago");
+        assert_eq!(
+            english.style(Style::Verbose).ends(Duration::minutes(65)),
+            "ends in 1 hour and 5 minutes"
+        );
+    }
+
+    #[test]
+    fn test_languages() {
+        let until = Duration::minutes(65);
+        let verbose = |language| Humanizer::new(language).style(Style::Verbose);
+        assert_eq!(
+            Humanizer::new(Language::German).ends(until),
+            "endet in 1 Std. 05 Min."
+        );
+        assert_eq!(
+            verbose(Language::German).starts(Duration::minutes(-1)),
+            "begann vor 1 Minute"
+        );
+        assert_eq!(
+            Humanizer::new(Language::French).starts(Duration::minutes(12)),
+            "dans 12 min"
+        );
+        assert_eq!(
+            verbose(Language::French).ends(until),
+            "se termine dans 1 heure et 5 minutes"
+        );
+        assert_eq!(
+            verbose(Language::French).duration(Duration::zero()),
+            "0 minute"
+        );
+        assert_eq!(
+            Humanizer::new(Language::Spanish).starts(Duration::minutes(-3)),
+            "empezó hace 3 min"
+        );
+        assert_eq!(
+            verbose(Language::Spanish).starts(Duration::hours(2)),
+            "en 2 horas"
+        );
+        assert_eq!(
+            Humanizer::new(Language::French).next("Tequatl"),
+            "ensuite Tequatl"
+        );
+    }
+
+    #[test]
+    fn test_parse_language() {
+        assert_eq!("de".parse(), Ok(Language::German));
+        assert_eq!(" FR ".parse(), Ok(Language::French));
+        for language in Language::all_keys().iter() {
+            assert_eq!(language.code().parse(), Ok(*language));
+        }
+        assert!("nl".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_countdown() {
+        let event = EventInstance {
+            schedule: Cow::Owned(EventSchedule {
+                name: "Pylons",
+                offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+                frequency: Duration::hours(2),
+                length: Duration::minutes(75),
+            }),
+            start_time: Duration::minutes(90),
+        };
+        let humanizer = Humanizer::default();
+        assert_eq!(humanizer.countdown(&event, Duration::minutes(78)), "in 12m");
+        assert_eq!(
+            humanizer.countdown(&event, Duration::minutes(100)),
+            "ends in 1h 05m"
+        );
+        assert_eq!(
+            humanizer.countdown(&event, Duration::minutes(168)),
+            "ended 3m ago"
+        );
+    }
+
+    #[test]
+    fn test_time() {
+        let time = Utc.with_ymd_and_hms(2022, 3, 1, 14, 5, 0).unwrap();
+        let tz = FixedOffset::east_opt(2 * 60 * 60).unwrap();
+        let humanizer = Humanizer::default();
+        assert_eq!(humanizer.time(time, &Utc), "14:05");
+        assert_eq!(humanizer.time(time, &tz), "16:05");
+        assert_eq!(
+            humanizer.style(Style::Verbose).time(time, &Utc),
+            "14:05 UTC"
+        );
+        assert_eq!(
+            humanizer.style(Style::Verbose).time(time, &tz),
+            "16:05 +02:00"
+        );
+    }
+
+    #[test]
+    fn test_event_debug() {
+        let event = EventInstance {
+            schedule: Cow::Owned(EventSchedule {
+                name: "Night",
+                offset: NaiveTime::from_hms_opt(23, 45, 0).unwrap(),
+                frequency: Duration::hours(2),
+                length: Duration::minutes(40),
+            }),
+            start_time: Duration::minutes(-15),
+        };
+        assert!(format!("{:?}", event).ends_with(", start: -15m"));
+    }
+}
+
+#[cfg(test)]
+mod idle_tests {
+    use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+
+    use crate::{
+        idle::{IdleFinder, Interval},
+        meta::MapMetaKind,
+        schedule::EventSchedule,
+    };
+
+    fn interval(start: i64, end: i64) -> Interval {
+        Interval {
+            start: Duration::minutes(start),
+            end: Duration::minutes(end),
+        }
+    }
+
+    #[test]
+    fn test_meta() {
+        // Events run back to back all day
+        let day_and_night = IdleFinder::meta(MapMetaKind::DayAndNight);
+        assert_eq!(
+            day_and_night
+                .between(Duration::zero(), Duration::days(1))
+                .count(),
+            0
+        );
+
+        // Every two hours The Desolation has Junudu Rising at :30 and Maws of Torment at 1:00
+        let desolation = IdleFinder::meta(MapMetaKind::TheDesolation);
+        let intervals = desolation
+            .between(Duration::zero(), Duration::hours(2))
+            .collect::<Vec<Interval>>();
+        assert_eq!(intervals.first(), Some(&interval(0, 30)));
+        assert!(intervals.iter().all(|i| i.start < i.end));
+    }
+
+    #[test]
+    fn test_events() {
+        // Pylons run from 01:30 until 02:45
+        let quiet = IdleFinder::events(MapMetaKind::AuricBasin, &["Pylons"]);
+        let intervals = quiet
+            .between(Duration::zero(), Duration::hours(6))
+            .collect::<Vec<Interval>>();
+        assert_eq!(
+            intervals,
+            [interval(45, 90), interval(165, 210), interval(285, 330)]
+        );
+        assert_eq!(intervals[0].length(), Duration::minutes(45));
+
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        assert_eq!(
+            intervals[0].start_datetime(date),
+            Utc.with_ymd_and_hms(2022, 3, 1, 0, 45, 0).unwrap()
+        );
+        assert_eq!(
+            intervals[0].end_datetime(date),
+            Utc.with_ymd_and_hms(2022, 3, 1, 1, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_window_edges() {
+        let quiet = IdleFinder::events(MapMetaKind::AuricBasin, &["Pylons"]);
+
+        // Starting and ending while the map is free
+        let intervals = quiet
+            .between(Duration::minutes(60), Duration::minutes(200))
+            .collect::<Vec<Interval>>();
+        assert_eq!(intervals, [interval(60, 90), interval(165, 200)]);
+
+        // Starting during pylons from the day before
+        let intervals = quiet
+            .between(Duration::minutes(-10), Duration::minutes(100))
+            .collect::<Vec<Interval>>();
+        assert_eq!(intervals, [interval(45, 90)]);
+
+        assert_eq!(
+            quiet
+                .between(Duration::minutes(100), Duration::minutes(100))
+                .count(),
+            0
+        );
+
+        // No events at all leaves the whole window free
+        let nothing = IdleFinder::events(MapMetaKind::AuricBasin, &[]);
+        let intervals = nothing
+            .between(Duration::zero(), Duration::hours(1))
+            .collect::<Vec<Interval>>();
+        assert_eq!(intervals, [interval(0, 60)]);
+    }
+
+    #[test]
+    fn test_overlapping_schedules() {
+        let schedules = vec![
+            EventSchedule {
+                name: "Long",
+                offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                frequency: Duration::hours(1),
+                length: Duration::minutes(30),
+            },
+            EventSchedule {
+                name: "Short",
+                offset: NaiveTime::from_hms_opt(0, 10, 0).unwrap(),
+                frequency: Duration::hours(1),
+                length: Duration::minutes(5),
+            },
+        ];
+        let intervals = IdleFinder::new(schedules)
+            .between(Duration::zero(), Duration::hours(2))
+            .collect::<Vec<Interval>>();
+        assert_eq!(intervals, [interval(30, 60), interval(90, 120)]);
+    }
 }
 
 #[cfg(test)]
@@ -109,7 +764,7 @@ mod meta_tests {
     #[test]
     #[rustfmt::skip]
     fn test_meta_iter() {
-        let mut meta_iter = MapMetaKind::WorldBosses.into_iter().time(NaiveTime::from_hms(8, 41, 0)).peekable();
+        let mut meta_iter = MapMetaKind::WorldBosses.into_iter().time(NaiveTime::from_hms_opt(8, 41, 0).unwrap()).peekable();
         assert_eq!(meta_iter.next().unwrap().schedule.name, "Fire Elemental");
         assert_eq!(meta_iter.next().unwrap().schedule.name, "Admiral Taidha Covington");
         assert_eq!(meta_iter.next().unwrap().schedule.name, "Great Jungle Wurm");
@@ -163,7 +818,7 @@ mod meta_tests {
     fn test_meta_iter_fns() {
         let mut meta_iter = MapMetaKind::LakeDoric
             .into_iter()
-            .time(NaiveTime::from_hms(4, 10, 0))
+            .time(NaiveTime::from_hms_opt(4, 10, 0).unwrap())
             .fast_forward(Duration::hours(1));
         assert_eq!(meta_iter.next().unwrap().schedule.name, "New Loamhurst");
         assert_eq!(meta_iter.next().unwrap().schedule.name, "Noran's Homestead");
@@ -173,54 +828,1968 @@ mod meta_tests {
     fn test_meta_now() {
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(0, 0, 0))
+            .time(NaiveTime::from_hms_opt(0, 0, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(0, 19, 0))
+            .time(NaiveTime::from_hms_opt(0, 19, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(0, 20, 0))
+            .time(NaiveTime::from_hms_opt(0, 20, 0).unwrap())
             .now();
         assert!(now.is_some());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(0, 40, 0))
+            .time(NaiveTime::from_hms_opt(0, 40, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(2, 0, 0))
+            .time(NaiveTime::from_hms_opt(2, 0, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(2, 19, 0))
+            .time(NaiveTime::from_hms_opt(2, 19, 0).unwrap())
             .now();
         assert!(now.is_none());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(2, 20, 0))
+            .time(NaiveTime::from_hms_opt(2, 20, 0).unwrap())
             .now();
         assert!(now.is_some());
 
         let now = MapMetaKind::LeyLineAnomaly
             .into_iter()
-            .time(NaiveTime::from_hms(2, 40, 0))
+            .time(NaiveTime::from_hms_opt(2, 40, 0).unwrap())
             .now();
         assert!(now.is_none());
     }
 }
 
+#[cfg(test)]
+mod chat_tests {
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use crate::{
+        chat::{Formatter, Platform},
+        clock::FixedClock,
+        event::EventInstance,
+        humanize::Language,
+        meta::MapMetaKind,
+    };
+
+    fn test_now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2022, 3, 1, 8, 41, 0).unwrap()
+    }
+
+    fn next_world_bosses(count: usize) -> Vec<EventInstance> {
+        MapMetaKind::WorldBosses
+            .iter_from(&FixedClock::new(test_now()))
+            .take(count)
+            .collect()
+    }
+
+    #[test]
+    fn test_discord() {
+        let message = Formatter::new(Platform::Discord, test_now())
+            .title("Next 2 world bosses")
+            .message(&next_world_bosses(2));
+        assert_eq!(
+            message,
+            "**Next 2 world bosses**\n\
+             Fire Elemental <t:1646124300:R>\n\
+             Admiral Taidha Covington <t:1646125200:R>"
+        );
+    }
+
+    #[test]
+    fn test_slack() {
+        let line = Formatter::new(Platform::Slack, test_now())
+            .line("{name} at {start} for {length}")
+            .event(&next_world_bosses(1)[0]);
+        assert_eq!(
+            line,
+            "Fire Elemental at <!date^1646124300^{time}|08:45 UTC> for 15m"
+        );
+    }
+
+    #[test]
+    fn test_plain() {
+        let formatter = Formatter::new(Platform::Plain, test_now())
+            .line("{name}: {start} - {end}, {relative}, {ends}");
+        assert_eq!(
+            formatter.event(&next_world_bosses(1)[0]),
+            "Fire Elemental: 08:45 UTC - 09:00 UTC, in 4m, ends in 19m"
+        );
+        assert_eq!(
+            formatter.meta_summary(MapMetaKind::WorldBosses),
+            "World Bosses: Claw of Jormag ends in 4m, next Fire Elemental in 4m"
+        );
+        assert_eq!(
+            formatter.meta_summary(MapMetaKind::HardWorldBosses),
+            "Hard World Bosses: next Karka Queen in 1h 49m"
+        );
+    }
+
+    #[test]
+    fn test_language() {
+        let formatter = Formatter::new(Platform::Plain, test_now())
+            .language(Language::German)
+            .line("{name}: {relative}, {ends}, {length}");
+        assert_eq!(
+            formatter.event(&next_world_bosses(1)[0]),
+            "Fire Elemental: in 4 Min., endet in 19 Min., 15 Min."
+        );
+        assert_eq!(
+            formatter.meta_summary(MapMetaKind::WorldBosses),
+            "World Bosses: Claw of Jormag endet in 4 Min., als Nächstes Fire Elemental in 4 Min."
+        );
+
+        // Slack only uses the text when it can't show the timestamp
+        let line = Formatter::new(Platform::Slack, test_now())
+            .language(Language::French)
+            .event(&next_world_bosses(1)[0]);
+        assert_eq!(line, "Fire Elemental <!date^1646124300^{ago}|dans 4 min>");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_discord_embed() {
+        let embed = Formatter::new(Platform::Plain, test_now())
+            .title("World bosses")
+            .discord_embed(&next_world_bosses(2));
+        assert_eq!(embed["title"], "World bosses");
+        assert_eq!(embed["fields"][0]["name"], "Fire Elemental");
+        assert_eq!(
+            embed["fields"][0]["value"],
+            "Fire Elemental <t:1646124300:R>"
+        );
+        assert_eq!(embed["fields"][1]["name"], "Admiral Taidha Covington");
+    }
+}
+
+#[cfg(test)]
+mod conflict_tests {
+    use chrono::Duration;
+
+    use crate::{conflict::ConflictChecker, meta::MapMetaKind, route::Target};
+
+    #[test]
+    fn test_conflicts() {
+        let checker = ConflictChecker::new(vec![
+            Target::new(MapMetaKind::HardWorldBosses, "Tequatl the Sunless"),
+            Target::new(MapMetaKind::DragonsEnd, "The Battle for the Jade Sea"),
+        ]);
+        let conflicts = checker.check(Duration::hours(18), Duration::hours(24));
+        assert_eq!(conflicts.len(), 1);
+
+        // Tequatl and the battle both start at 19:00
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.first.event.schedule.name, "Tequatl the Sunless");
+        assert_eq!(conflict.first.event.start_time, Duration::hours(19));
+        assert_eq!(
+            conflict.second.event.schedule.name,
+            "The Battle for the Jade Sea"
+        );
+        assert_eq!(conflict.overlap, Duration::minutes(30));
+
+        assert!(conflict.first_alternatives.is_empty());
+        let alternatives = conflict
+            .second_alternatives
+            .iter()
+            .map(|stop| stop.event.start_time)
+            .collect::<Vec<Duration>>();
+        assert_eq!(alternatives, [Duration::hours(21), Duration::hours(23)]);
+    }
+
+    #[test]
+    fn test_travel() {
+        // Back to back bosses only conflict when there's travel between them
+        let targets = vec![
+            Target::new(MapMetaKind::WorldBosses, "Admiral Taidha Covington"),
+            Target::new(MapMetaKind::WorldBosses, "Svanir Shaman Chief"),
+        ];
+        let checker = ConflictChecker::new(targets.clone());
+        assert!(checker
+            .check(Duration::zero(), Duration::hours(6))
+            .is_empty());
+
+        let mut checker = ConflictChecker::new(targets);
+        checker.targets[1].travel = Duration::minutes(5);
+        let conflicts = checker.check(Duration::zero(), Duration::hours(6));
+        let starts = conflicts
+            .iter()
+            .map(|conflict| conflict.first.event.start_time)
+            .collect::<Vec<Duration>>();
+        // Svanir follows the Admiral at 00:00 and 06:00, but only the first is in the window
+        assert_eq!(starts, [Duration::zero()]);
+        assert_eq!(conflicts[0].overlap, Duration::minutes(5));
+        assert_eq!(conflicts[0].second.depart, Duration::minutes(10));
+    }
+
+    #[test]
+    fn test_same_target() {
+        // Occurrences of the same event don't conflict, even when they overlap
+        let checker = ConflictChecker::new(vec![Target::new(
+            MapMetaKind::DragonsStand,
+            "Start advancing on the Blighting Towers",
+        )
+        .travel(Duration::minutes(30))]);
+        assert!(checker
+            .check(Duration::zero(), Duration::days(1))
+            .is_empty());
+    }
+}
+
+#[cfg(test)]
+mod cursor_tests {
+    use alloc::borrow::Cow;
+
+    use chrono::{Duration, NaiveTime};
+
+    use crate::{cursor::Cursor, cycle::CycleTable, meta::MapMetaKind, schedule::EventSchedule};
+
+    fn minutes(cursor: &mut Cursor, count: usize) -> Vec<i64> {
+        cursor
+            .take(count)
+            .map(|event| event.start_time.num_minutes())
+            .collect()
+    }
+
+    #[test]
+    fn test_seek() {
+        let mut cursor = MapMetaKind::DayAndNight.into_iter();
+        cursor.seek(Duration::minutes(100));
+        assert_eq!(cursor.next().unwrap().schedule.name, "Night");
+        assert_eq!(cursor.position(), Duration::minutes(105));
+
+        // Seeking backwards and past the current day
+        cursor.seek(Duration::minutes(-20));
+        assert_eq!(cursor.next().unwrap().start_time, Duration::minutes(-15));
+        cursor.seek(Duration::days(2));
+        assert_eq!(
+            cursor.next().unwrap().start_time,
+            Duration::days(2) + Duration::minutes(25)
+        );
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut cursor = MapMetaKind::WorldBosses
+            .into_iter()
+            .time(NaiveTime::from_hms_opt(12, 0, 0).unwrap());
+        let peeked = cursor.peek().unwrap();
+        assert_eq!(cursor.peek().unwrap().start_time, peeked.start_time);
+        let next = cursor.next().unwrap();
+        assert_eq!(next.start_time, peeked.start_time);
+        assert_eq!(next.schedule.name, peeked.schedule.name);
+    }
+
+    #[test]
+    fn test_step_back() {
+        let schedule = EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        };
+        let mut cursor = schedule.iter();
+        assert_eq!(minutes(&mut cursor, 3), [20, 80, 140]);
+        assert_eq!(cursor.step_back().unwrap().start_time.num_minutes(), 140);
+        assert_eq!(cursor.step_back().unwrap().start_time.num_minutes(), 80);
+
+        // The cursor is left where seeking would give the same events
+        assert_eq!(cursor.position(), Duration::minutes(79));
+        assert!(cursor.now().is_none());
+        let mut seeked = schedule.iter();
+        seeked.seek(cursor.position());
+        assert_eq!(minutes(&mut seeked, 2), [80, 140]);
+        assert_eq!(cursor.next().unwrap().start_time.num_minutes(), 80);
+        assert_eq!(cursor.position(), Duration::minutes(80));
+
+        // Stepping back past UTC 00:00 gives events from the day before
+        assert_eq!(cursor.step_back().unwrap().start_time.num_minutes(), 80);
+        assert_eq!(cursor.step_back().unwrap().start_time.num_minutes(), 20);
+        assert_eq!(cursor.step_back().unwrap().start_time.num_minutes(), -40);
+        assert_eq!(minutes(&mut cursor, 2), [-40, 20]);
+    }
+
+    #[test]
+    fn test_nth() {
+        for kind in MapMetaKind::all_keys() {
+            let start = kind
+                .into_iter()
+                .time(NaiveTime::from_hms_opt(7, 13, 0).unwrap());
+            for n in [0, 1, 5, 17, 100] {
+                let mut skipped = start.clone();
+                let mut stepped = start.clone();
+                let expected = stepped.by_ref().take(n + 1).last().unwrap();
+                let event = skipped.nth(n).unwrap();
+                assert_eq!(event.start_time, expected.start_time, "{:?}", kind);
+                assert_eq!(event.schedule.name, expected.schedule.name, "{:?}", kind);
+                assert_eq!(
+                    skipped.next().unwrap().start_time,
+                    stepped.next().unwrap().start_time
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_lookahead() {
+        // Day and night repeats every two hours, with four events in each
+        let mut cursor = MapMetaKind::DayAndNight.into_iter();
+        let event = cursor.nth(4 * 12 * 365 * 1000).unwrap();
+        assert_eq!(event.schedule.name, "Dawn");
+        assert_eq!(
+            event.start_time,
+            Duration::days(365 * 1000) + Duration::minutes(25)
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut cursor = Cursor::new(Cow::Owned(CycleTable::new(Vec::new())));
+        assert!(cursor.peek().is_none());
+        assert!(cursor.next().is_none());
+        assert!(cursor.nth(10).is_none());
+        assert!(cursor.step_back().is_none());
+        assert!(cursor.now().is_none());
+    }
+}
+
+#[cfg(test)]
+mod cycle_tests {
+    use alloc::{borrow::Cow, vec, vec::Vec};
+
+    use chrono::{Duration, NaiveTime, Timelike};
+
+    use crate::{cursor::Cursor, cycle::CycleTable, meta::MapMetaKind, schedule::EventSchedule};
+
+    /// Minutes after UTC 00:00 of a schedule's first start, and its frequency
+    fn minutes(schedule: &EventSchedule) -> (i64, i64) {
+        let frequency = schedule.frequency.num_minutes();
+        let offset = schedule.offset.num_seconds_from_midnight() as i64 / 60;
+        (offset % frequency, frequency)
+    }
+
+    #[test]
+    fn test_matches_schedules() {
+        for kind in MapMetaKind::all_keys() {
+            let schedules = kind.info().schedules;
+            let table = kind.cycle_table();
+            for time in (-60..3 * 24 * 60).step_by(7) {
+                let expected_next = schedules
+                    .iter()
+                    .map(|schedule| {
+                        let (offset, frequency) = minutes(schedule);
+                        let start =
+                            offset + ((time - offset).div_euclid(frequency) + 1) * frequency;
+                        (start, schedule.name)
+                    })
+                    .min_by_key(|(start, _)| *start);
+                let next = table.next(Duration::minutes(time)).unwrap();
+                assert_eq!(
+                    Some((next.start_time.num_minutes(), next.schedule.name)),
+                    expected_next,
+                    "{:?} at {}",
+                    kind,
+                    time
+                );
+
+                let expected_now = schedules.iter().find_map(|schedule| {
+                    let (offset, frequency) = minutes(schedule);
+                    let start = offset + (time - offset).div_euclid(frequency) * frequency;
+                    (time < start + schedule.length.num_minutes()).then_some((start, schedule.name))
+                });
+                let now = table.now(Duration::minutes(time));
+                assert_eq!(
+                    now.map(|now| (now.start_time.num_minutes(), now.schedule.name)),
+                    expected_now,
+                    "{:?} at {}",
+                    kind,
+                    time
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_period() {
+        let table = CycleTable::new(vec![
+            EventSchedule {
+                name: "A",
+                offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                frequency: Duration::minutes(40),
+                length: Duration::minutes(10),
+            },
+            EventSchedule {
+                name: "B",
+                offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+                frequency: Duration::hours(1),
+                length: Duration::minutes(30),
+            },
+        ]);
+        assert_eq!(table.period(), Some(Duration::hours(2)));
+        assert!(CycleTable::new(Vec::new()).next(Duration::zero()).is_none());
+
+        // A and B both start at 01:20, and A comes first
+        let next = table.next(Duration::minutes(61)).unwrap();
+        assert_eq!(
+            (next.schedule.name, next.start_time.num_minutes()),
+            ("A", 80)
+        );
+        let next = table.next(Duration::minutes(80)).unwrap();
+        assert_eq!(
+            (next.schedule.name, next.start_time.num_minutes()),
+            ("A", 120)
+        );
+        let now = table.now(Duration::minutes(95)).unwrap();
+        assert_eq!((now.schedule.name, now.start_time.num_minutes()), ("B", 80));
+
+        // B started the day before and is still going
+        let now = table.now(Duration::minutes(-15)).unwrap();
+        assert_eq!(
+            (now.schedule.name, now.start_time.num_minutes()),
+            ("B", -40)
+        );
+    }
+
+    #[test]
+    fn test_coprime_frequencies() {
+        // Only repeats every 1439 * 1440 minutes, so the schedules are merged instead of listed
+        let schedules = vec![
+            EventSchedule {
+                name: "A",
+                offset: NaiveTime::from_hms_opt(0, 5, 0).unwrap(),
+                frequency: Duration::minutes(1439),
+                length: Duration::minutes(30),
+            },
+            EventSchedule {
+                name: "B",
+                offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+                frequency: Duration::minutes(1440),
+                length: Duration::minutes(60),
+            },
+        ];
+        let table = CycleTable::new(schedules.clone());
+        assert_eq!(table.period(), None);
+
+        let days = 1440 * 1440;
+        for time in (-3 * days..3 * days)
+            .step_by(997)
+            .chain(days - 2000..days + 2000)
+        {
+            let expected_next = schedules
+                .iter()
+                .map(|schedule| {
+                    let (offset, frequency) = minutes(schedule);
+                    let start = offset + ((time - offset).div_euclid(frequency) + 1) * frequency;
+                    (start, schedule.name)
+                })
+                .min_by_key(|(start, _)| *start);
+            let next = table.next(Duration::minutes(time)).unwrap();
+            assert_eq!(
+                Some((next.start_time.num_minutes(), next.schedule.name)),
+                expected_next,
+                "at {}",
+                time
+            );
+
+            let expected_now = schedules.iter().find_map(|schedule| {
+                let (offset, frequency) = minutes(schedule);
+                let start = offset + (time - offset).div_euclid(frequency) * frequency;
+                (time < start + schedule.length.num_minutes()).then_some((start, schedule.name))
+            });
+            let now = table.now(Duration::minutes(time));
+            assert_eq!(
+                now.map(|now| (now.start_time.num_minutes(), now.schedule.name)),
+                expected_now,
+                "at {}",
+                time
+            );
+        }
+
+        // A catches up with B on the fifth day, when both start at once and A comes first
+        let events = Cursor::new(Cow::Owned(CycleTable::new(schedules)))
+            .fast_forward(Duration::minutes(5 * 1440 - 1))
+            .take(2)
+            .map(|event| (event.schedule.name, event.start_time.num_minutes()))
+            .collect::<Vec<_>>();
+        assert_eq!(events, vec![("A", 5 * 1440), ("B", 5 * 1440)]);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod alloc_tests {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        hint::black_box,
+    };
+
+    use chrono::{Duration, NaiveTime};
+
+    use crate::{meta::MapMetaKind, schedule::EventSchedule};
+
+    /// Counts the allocations made on each thread, so tests running in parallel don't interfere
+    struct CountingAllocator;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    fn allocations(f: impl FnOnce()) -> usize {
+        let before = ALLOCATIONS.with(Cell::get);
+        f();
+        ALLOCATIONS.with(Cell::get) - before
+    }
+
+    #[test]
+    fn test_builtin_iteration_is_allocation_free() {
+        assert_eq!(
+            allocations(|| drop(black_box(Vec::<u8>::with_capacity(1)))),
+            1
+        );
+
+        // The tables are built the first time they're used
+        MapMetaKind::DayAndNight.cycle_table();
+
+        let count = allocations(|| {
+            for kind in MapMetaKind::all_keys() {
+                let mut iter = kind
+                    .into_iter()
+                    .time(NaiveTime::from_hms_opt(23, 30, 0).unwrap());
+                black_box(iter.now());
+                for event in iter.by_ref().take(100) {
+                    assert!(!event.schedule.name.is_empty());
+                }
+                let _ = kind.info().schedules.len();
+            }
+        });
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_user_schedules() {
+        let schedule = EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        };
+        let event = schedule.iter().next().unwrap();
+        assert_eq!(event.schedule.name, "Reoccurring event");
+        assert_eq!(event.start_time, Duration::minutes(20));
+    }
+}
+
+#[cfg(test)]
+mod error_tests {
+    use alloc::borrow::Cow;
+
+    use chrono::{Duration, NaiveTime};
+
+    use crate::{
+        category::Category, cycle::CycleTable, error::Error, meta::MapMeta,
+        reminder::ReminderPlanner, schedule::EventSchedule,
+    };
+
+    fn schedule(frequency: Duration, length: Duration) -> EventSchedule {
+        EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+            frequency,
+            length,
+        }
+    }
+
+    #[test]
+    fn test_schedule_new() {
+        let offset = NaiveTime::from_hms_opt(0, 20, 0).unwrap();
+        assert!(
+            EventSchedule::new("Event", offset, Duration::hours(1), Duration::minutes(15)).is_ok()
+        );
+
+        assert_eq!(
+            EventSchedule::new("Event", offset, Duration::zero(), Duration::minutes(15))
+                .unwrap_err(),
+            Error::InvalidFrequency {
+                name: "Event",
+                frequency: Duration::zero()
+            }
+        );
+        assert!(matches!(
+            EventSchedule::new(
+                "Event",
+                offset,
+                Duration::seconds(30),
+                Duration::minutes(15)
+            ),
+            Err(Error::InvalidFrequency { .. })
+        ));
+        assert!(matches!(
+            EventSchedule::new(
+                "Event",
+                offset,
+                Duration::seconds(90),
+                Duration::minutes(15)
+            ),
+            Err(Error::InvalidFrequency { .. })
+        ));
+        assert!(matches!(
+            EventSchedule::new("Event", offset, Duration::hours(1), Duration::minutes(-15)),
+            Err(Error::InvalidLength { .. })
+        ));
+        assert_eq!(
+            EventSchedule::new(
+                "Event",
+                NaiveTime::from_hms_opt(0, 20, 30).unwrap(),
+                Duration::hours(1),
+                Duration::minutes(15)
+            )
+            .unwrap_err()
+            .to_string(),
+            "Event starts at 00:20:30, which is not on a whole minute"
+        );
+    }
+
+    #[test]
+    fn test_meta_new() {
+        let valid = schedule(Duration::hours(1), Duration::minutes(15));
+        let meta = MapMeta::new("Meta", Category::CoreTyria, vec![valid.clone()]).unwrap();
+        assert_eq!(
+            meta.into_iter().next().unwrap().start_time,
+            Duration::hours(1)
+        );
+
+        assert_eq!(
+            MapMeta::new("Meta", Category::CoreTyria, Vec::new()).err(),
+            Some(Error::NoSchedules)
+        );
+        assert!(matches!(
+            MapMeta::new(
+                "Meta",
+                Category::CoreTyria,
+                vec![
+                    valid.clone(),
+                    schedule(Duration::zero(), Duration::minutes(15))
+                ]
+            ),
+            Err(Error::InvalidFrequency { .. })
+        ));
+        assert!(schedule(Duration::minutes(1), Duration::weeks(4))
+            .check()
+            .is_ok());
+        assert!(matches!(
+            MapMeta::new(
+                "Meta",
+                Category::CoreTyria,
+                vec![schedule(
+                    Duration::minutes(1),
+                    Duration::weeks(4) + Duration::minutes(1)
+                )]
+            ),
+            Err(Error::LengthTooLong { .. })
+        ));
+
+        // Only repeats every 10007 * 10009 minutes, which is still fine
+        let uneven = vec![
+            schedule(Duration::minutes(10007), Duration::minutes(15)),
+            schedule(Duration::minutes(10009), Duration::minutes(15)),
+        ];
+        assert!(MapMeta::new("Meta", Category::CoreTyria, uneven.clone()).is_ok());
+        assert_eq!(CycleTable::try_new(uneven).unwrap().period(), None);
+    }
+
+    #[test]
+    fn test_invalid_schedules_dont_panic() {
+        let empty = MapMeta {
+            name: "Meta",
+            category: Category::CoreTyria,
+            schedules: Cow::Owned(Vec::new()),
+        };
+        let mut iter = empty.into_iter();
+        assert!(iter.now().is_none());
+        assert!(iter.next().is_none());
+
+        for frequency in [Duration::zero(), Duration::seconds(30), Duration::hours(-1)] {
+            let mut iter = schedule(frequency, Duration::minutes(15)).into_iter();
+            assert!(iter.now().is_none());
+            assert!(iter.next().is_none());
+        }
+
+        // Events that would take ages to look back over are left out too
+        let endless = schedule(Duration::minutes(1), Duration::milliseconds(i64::MAX));
+        let mut iter = endless.into_iter();
+        assert!(iter.now().is_none());
+        assert!(iter.next().is_none());
+
+        // The longest events allowed are found quickly
+        let long = schedule(Duration::minutes(1), Duration::weeks(4));
+        let iter = long.into_iter().fast_forward(Duration::days(3));
+        assert_eq!(iter.now().unwrap().start_time, Duration::days(3));
+
+        // Schedules that can't be iterated are left out, and the rest still are, even when they
+        // repeat too rarely to list
+        let meta = MapMeta {
+            name: "Meta",
+            category: Category::CoreTyria,
+            schedules: Cow::Owned(vec![
+                schedule(Duration::zero(), Duration::minutes(15)),
+                schedule(Duration::minutes(10007), Duration::minutes(15)),
+                schedule(Duration::minutes(10009), Duration::minutes(15)),
+            ]),
+        };
+        let starts = meta
+            .into_iter()
+            .take(4)
+            .map(|event| event.start_time.num_minutes())
+            .collect::<Vec<_>>();
+        assert_eq!(starts, vec![10007, 10009, 20014, 20018]);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        // Half a minute before UTC 00:00 is still the day before
+        let schedule = schedule(Duration::hours(1), Duration::minutes(15));
+        let mut iter = schedule.iter().fast_forward(Duration::seconds(-30));
+        assert!(iter.now().is_none());
+        assert_eq!(iter.next().unwrap().start_time, Duration::zero());
+
+        let mut iter = schedule.iter().fast_forward(Duration::seconds(-3030));
+        assert_eq!(iter.now().unwrap().start_time, Duration::hours(-1));
+        assert_eq!(iter.next().unwrap().start_time, Duration::zero());
+
+        // Past the end of time there are no more events
+        let mut iter = schedule
+            .iter()
+            .fast_forward(Duration::milliseconds(i64::MAX))
+            .fast_forward(Duration::days(1));
+        assert!(iter.next().is_none());
+        assert!(schedule.iter().nth(usize::MAX).is_none());
+
+        // Collecting the last events doesn't reserve room for endless ones
+        let last = schedule
+            .iter()
+            .fast_forward(Duration::milliseconds(i64::MAX) - Duration::hours(3))
+            .collect::<Vec<_>>();
+        assert!(!last.is_empty() && last.len() <= 3);
+
+        // Reminders stop there too, even when only the lead time goes past it
+        let planner = ReminderPlanner::new(vec![schedule.clone()], vec![Duration::minutes(10)]);
+        let mut reminders = planner
+            .into_iter()
+            .fast_forward(Duration::milliseconds(i64::MAX))
+            .fast_forward(Duration::days(1));
+        assert!(reminders.next().is_none());
+
+        let planner = ReminderPlanner::new(vec![schedule.clone()], vec![Duration::minutes(10)]);
+        let mut reminders = planner
+            .into_iter()
+            .fast_forward(Duration::milliseconds(i64::MAX) - Duration::minutes(5));
+        assert!(reminders.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod clock_tests {
+    use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
+
+    use crate::{clock::FixedClock, meta::MapMetaKind};
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_manual_clock() {
+        use chrono::Duration;
+
+        use crate::{
+            clock::{Clock, ManualClock},
+            schedule::EventSchedule,
+        };
+
+        let clock = ManualClock::new(Utc.with_ymd_and_hms(2022, 3, 1, 0, 50, 0).unwrap());
+        let test_event_schedule = EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        };
+
+        let event = test_event_schedule.iter_from(&clock).next().unwrap();
+        assert_eq!(event.start_time.num_minutes(), 80);
+
+        clock.advance(Duration::hours(1));
+        let event = test_event_schedule.iter_from(&clock).next().unwrap();
+        assert_eq!(event.start_time.num_minutes(), 140);
+
+        clock.set(Utc.with_ymd_and_hms(2022, 3, 2, 0, 0, 0).unwrap());
+        assert_eq!(
+            clock.now(),
+            Utc.with_ymd_and_hms(2022, 3, 2, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_fixed_clock() {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2022, 3, 1, 8, 41, 0).unwrap());
+        let mut meta_iter = MapMetaKind::WorldBosses.iter_from(&clock);
+        assert_eq!(meta_iter.next().unwrap().schedule.name, "Fire Elemental");
+        assert_eq!(meta_iter.next().unwrap().schedule.name, "Admiral Taidha Covington");
+
+        let now = MapMetaKind::LeyLineAnomaly.iter_from(&clock).now();
+        assert!(now.is_none());
+    }
+
+    #[test]
+    fn test_event_datetime() {
+        let date = NaiveDate::from_ymd_opt(2022, 3, 1).unwrap();
+        let event = MapMetaKind::HardWorldBosses
+            .into_iter()
+            .time(NaiveTime::from_hms_opt(23, 0, 0).unwrap())
+            .next()
+            .unwrap();
+        assert_eq!(event.schedule.name, "Tequatl the Sunless");
+        assert_eq!(
+            event.start_datetime(date),
+            Utc.with_ymd_and_hms(2022, 3, 2, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            event.end_datetime(date),
+            Utc.with_ymd_and_hms(2022, 3, 2, 0, 30, 0).unwrap()
+        );
+    }
+}
+
+#[cfg(test)]
+mod ical_tests {
+    use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+
+    use crate::{clock::FixedClock, ical::Calendar, meta::MapMetaKind, schedule::EventSchedule};
+
+    fn test_date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2022, 3, 1).unwrap()
+    }
+
+    fn test_clock() -> FixedClock {
+        FixedClock::new(Utc.with_ymd_and_hms(2022, 2, 27, 14, 5, 9).unwrap())
+    }
+
+    #[test]
+    fn test_calendar_rrule() {
+        let calendar = Calendar::new(test_date(), &test_clock())
+            .meta(MapMetaKind::Dragonstorm.info())
+            .alarm(Duration::minutes(10))
+            .to_string();
+        assert_eq!(
+            calendar,
+            "BEGIN:VCALENDAR\r\n\
+             VERSION:2.0\r\n\
+             PRODID:-//purplg//gw2timers//EN\r\n\
+             CALSCALE:GREGORIAN\r\n\
+             BEGIN:VEVENT\r\n\
+             UID:dragonstorm-dragonstorm-public-0060-120@gw2timers\r\n\
+             DTSTAMP:20220227T140509Z\r\n\
+             DTSTART:20220301T010000Z\r\n\
+             DURATION:PT20M\r\n\
+             RRULE:FREQ=HOURLY;INTERVAL=2\r\n\
+             SUMMARY:Dragonstorm (Public) (Dragonstorm)\r\n\
+             CATEGORIES:Dragonstorm\r\n\
+             BEGIN:VALARM\r\n\
+             ACTION:DISPLAY\r\n\
+             DESCRIPTION:Dragonstorm (Public) (Dragonstorm)\r\n\
+             TRIGGER:-PT10M\r\n\
+             END:VALARM\r\n\
+             END:VEVENT\r\n\
+             END:VCALENDAR\r\n"
+        );
+    }
+
+    #[test]
+    fn test_calendar_alarm_after_start() {
+        let calendar = Calendar::new(test_date(), &test_clock())
+            .meta(MapMetaKind::Dragonstorm.info())
+            .alarm(Duration::minutes(-5))
+            .alarm(Duration::zero())
+            .to_string();
+        assert!(calendar.contains("TRIGGER:PT5M\r\n"));
+        assert!(calendar.contains("TRIGGER:-PT0S\r\n"));
+    }
+
+    #[test]
+    fn test_calendar_rrule_kinds() {
+        let calendar = Calendar::new(test_date(), &test_clock())
+            .meta(MapMetaKind::HardWorldBosses.info())
+            .meta(MapMetaKind::DragonsEnd.info())
+            .to_string();
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 18 + 5);
+        assert_eq!(calendar.matches("RRULE:FREQ=DAILY;INTERVAL=1").count(), 18);
+        assert!(calendar.contains("DTSTART:20220301T000000Z\r\n"));
+        assert!(calendar.contains("DURATION:PT1H\r\n"));
+        assert!(calendar.contains("SUMMARY:Jade Maw (Dragon's End)\r\n"));
+    }
+
+    #[test]
+    fn test_calendar_expanded() {
+        // 7 hours doesn't line up with days, so each occurrence is written out
+        let calendar = Calendar::new(test_date(), &test_clock())
+            .days(2)
+            .schedule(EventSchedule {
+                name: "Odd, event; with a long name that needs to be folded over lines",
+                offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+                frequency: Duration::hours(7),
+                length: Duration::minutes(90),
+            })
+            .to_string();
+        assert_eq!(calendar.matches("BEGIN:VEVENT").count(), 7);
+        assert!(!calendar.contains("RRULE"));
+        assert!(calendar.contains("DTSTART:20220302T050000Z\r\n"));
+        assert!(calendar.contains("DURATION:PT1H30M\r\n"));
+        assert!(calendar.contains(
+            "UID:event-odd-event-with-a-long-name-that-needs-to-be-folded-over-lines-006\r\n \
+             0-420-20220301T010000Z@gw2timers\r\n"
+        ));
+        assert!(calendar.contains(
+            "SUMMARY:Odd\\, event\\; with a long name that needs to be folded over lines\r\n"
+        ));
+        assert!(calendar.lines().all(|line| line.len() <= 75));
+    }
+}
+
+#[cfg(all(test, feature = "notify"))]
+mod notify_tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::{Duration as StdDuration, Instant},
+    };
+
+    use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+
+    use crate::{
+        clock::{Clock, ManualClock},
+        notify::{
+            double_backoff, Alert, Dispatcher, EmailNotifier, LogNotifier, Notifier, NotifyError,
+            PushNotifier, RateLimit, Retry, WebhookFormat, WebhookNotifier,
+        },
+        reminder::ReminderPlanner,
+        schedule::EventSchedule,
+    };
+
+    fn test_alert() -> Alert {
+        Alert {
+            title: String::from("Tequatl the Sunless"),
+            message: String::from("Tequatl the Sunless starts in 10m at 11:30 UTC"),
+        }
+    }
+
+    /// Start a server that answers each request with the next response, and sends back each
+    /// request it receives
+    fn mock_http(responses: Vec<&'static str>) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                sender.send(request).unwrap();
+                write!(&stream, "{}", response).unwrap();
+            }
+        });
+        (url, receiver)
+    }
+
+    const OK: &str = "HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n";
+    const TOO_MANY: &str =
+        "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 0\r\nContent-Length: 0\r\n\r\n";
+    const FAILED: &str = "HTTP/1.1 500 Internal Server Error\r\nContent-Length: 0\r\n\r\n";
+
+    #[test]
+    fn test_webhook() {
+        let (url, requests) = mock_http(vec![OK, OK]);
+        let webhook = WebhookNotifier::new(&format!("{}/hook", url), WebhookFormat::Discord);
+        webhook.notify(&test_alert()).unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /hook HTTP/1.1\r\n"));
+        assert!(request.ends_with(
+            r#"{"content":"**Tequatl the Sunless**\nTequatl the Sunless starts in 10m at 11:30 UTC"}"#
+        ));
+
+        let webhook = WebhookNotifier::new(&url, WebhookFormat::Slack);
+        webhook.notify(&test_alert()).unwrap();
+        assert!(requests.recv().unwrap().ends_with(
+            r#"{"text":"*Tequatl the Sunless*\nTequatl the Sunless starts in 10m at 11:30 UTC"}"#
+        ));
+    }
+
+    #[test]
+    fn test_push() {
+        let (url, requests) = mock_http(vec![OK, OK]);
+        let ntfy = PushNotifier::Ntfy {
+            url: format!("{}/raids", url),
+        };
+        ntfy.notify(&test_alert()).unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /raids HTTP/1.1\r\n"));
+        assert!(request.contains("Title: Tequatl the Sunless\r\n"));
+        assert!(request.ends_with("\r\n\r\nTequatl the Sunless starts in 10m at 11:30 UTC"));
+
+        let gotify = PushNotifier::Gotify {
+            url: format!("{}/", url),
+            token: String::from("secret"),
+        };
+        gotify.notify(&test_alert()).unwrap();
+        let request = requests.recv().unwrap();
+        assert!(request.starts_with("POST /message HTTP/1.1\r\n"));
+        assert!(request.contains("X-Gotify-Key: secret\r\n"));
+        assert!(request.contains(r#""title":"Tequatl the Sunless""#));
+    }
+
+    #[test]
+    fn test_retry() {
+        let (url, requests) = mock_http(vec![FAILED, TOO_MANY, OK]);
+        let webhook = Retry::new(
+            WebhookNotifier::new(&url, WebhookFormat::Discord),
+            3,
+            StdDuration::ZERO,
+        );
+        webhook.notify(&test_alert()).unwrap();
+        assert_eq!(requests.iter().take(3).count(), 3);
+
+        let (url, _requests) = mock_http(vec![FAILED, FAILED]);
+        let webhook = Retry::new(
+            WebhookNotifier::new(&url, WebhookFormat::Discord),
+            2,
+            StdDuration::ZERO,
+        );
+        match webhook.notify(&test_alert()) {
+            Err(NotifyError::Status(500)) => {}
+            result => panic!("expected server error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_retry_backoff() {
+        let second = StdDuration::from_secs(1);
+        let hour = StdDuration::from_secs(60 * 60);
+        assert_eq!(double_backoff(second, second), 2 * second);
+        assert_eq!(double_backoff(hour - second, second), hour);
+
+        // Doubling forever never overflows
+        let mut backoff = second;
+        for _ in 0..200 {
+            backoff = double_backoff(backoff, second);
+        }
+        assert_eq!(backoff, hour);
+        assert_eq!(
+            double_backoff(StdDuration::MAX, StdDuration::MAX),
+            StdDuration::MAX
+        );
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let log = RateLimit::new(LogNotifier::new(Vec::new()), StdDuration::from_millis(50));
+        let start = Instant::now();
+        log.notify(&test_alert()).unwrap();
+        log.notify(&test_alert()).unwrap();
+        log.notify(&test_alert()).unwrap();
+        assert!(start.elapsed() >= StdDuration::from_millis(100));
+    }
+
+    #[test]
+    fn test_email() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let server = listener.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut received = Vec::new();
+            let reply = |line: &str| write!(&stream, "{}\r\n", line).unwrap();
+            reply("220 mock ready");
+            let mut data = false;
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 {
+                    break;
+                }
+                let line = line.trim_end().to_string();
+                received.push(line.clone());
+                match line.as_str() {
+                    _ if data && line == "." => {
+                        data = false;
+                        reply("250 queued");
+                    }
+                    _ if data => {}
+                    "DATA" => {
+                        data = true;
+                        reply("354 go ahead");
+                    }
+                    "QUIT" => {
+                        reply("221 bye");
+                        break;
+                    }
+                    _ if line.starts_with("EHLO") => {
+                        reply("250-mock");
+                        reply("250 SIZE 1000");
+                    }
+                    _ => reply("250 ok"),
+                }
+            }
+            received
+        });
+
+        let email = EmailNotifier::new(&server, "bot@example.com", &["guild@example.com"]);
+        let alert = Alert {
+            title: String::from("Reminder\r\nBcc: everyone@example.com"),
+            message: String::from("first line\n.second line"),
+        };
+        email.notify(&alert).unwrap();
+        let received = handle.join().unwrap();
+        assert_eq!(received[0], "EHLO gw2timers");
+        assert_eq!(received[1], "MAIL FROM:<bot@example.com>");
+        assert_eq!(received[2], "RCPT TO:<guild@example.com>");
+        assert_eq!(received[3], "DATA");
+        assert!(received.contains(&String::from(
+            "Subject: Reminder  Bcc: everyone@example.com"
+        )));
+        assert!(!received.iter().any(|line| line.starts_with("Bcc:")));
+        assert!(received.contains(&String::from("..second line")));
+        assert_eq!(received.last().unwrap(), "QUIT");
+    }
+
+    #[test]
+    fn test_email_invalid_address() {
+        // Rejected before connecting, so the server is never reached
+        let email = EmailNotifier::new(
+            "127.0.0.1:1",
+            "bot@example.com",
+            &["guild@example.com>\r\nRCPT TO:<everyone@example.com"],
+        );
+        match email.notify(&test_alert()) {
+            Err(NotifyError::InvalidAddress(address)) => assert!(address.contains('\n')),
+            result => panic!("expected invalid address, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_dispatcher() {
+        let schedule = EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        };
+        let planner = ReminderPlanner::new(vec![schedule], vec![Duration::minutes(10)]);
+        let start = Utc.with_ymd_and_hms(2022, 3, 1, 23, 0, 0).unwrap();
+        let mut dispatcher = Dispatcher::new(LogNotifier::new(Vec::new()), planner, start);
+
+        assert_eq!(
+            dispatcher.next_due(),
+            Some(Utc.with_ymd_and_hms(2022, 3, 1, 23, 10, 0).unwrap())
+        );
+        assert!(dispatcher.poll(start).is_empty());
+
+        let alerts = dispatcher.poll(Utc.with_ymd_and_hms(2022, 3, 2, 0, 10, 0).unwrap());
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(
+            alerts[1].as_ref().unwrap(),
+            &Alert {
+                title: String::from("Reoccurring event"),
+                message: String::from("Reoccurring event starts in 10m at 00:20 UTC"),
+            }
+        );
+        assert_eq!(
+            Alert::from_reminder(
+                &ReminderPlanner::new(
+                    vec![EventSchedule {
+                        name: "Reoccurring event",
+                        offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+                        frequency: Duration::hours(1),
+                        length: Duration::minutes(15),
+                    }],
+                    vec![Duration::minutes(90)],
+                )
+                .into_iter()
+                .next()
+                .unwrap(),
+                NaiveDate::from_ymd_opt(2022, 3, 1).unwrap()
+            )
+            .message,
+            "Reoccurring event starts in 1h 30m at 02:20 UTC"
+        );
+    }
+
+    #[test]
+    fn test_run_with() {
+        let schedule = EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        };
+        let planner = ReminderPlanner::new(vec![schedule], vec![Duration::minutes(10)]);
+
+        // On the last day there is, so running stops after the reminders due before its end
+        let start = DateTime::<Utc>::MAX_UTC
+            .date_naive()
+            .and_hms_opt(22, 0, 0)
+            .unwrap()
+            .and_utc();
+        let clock = ManualClock::new(start);
+        let mut waits = Vec::new();
+        let mut alerts = Vec::new();
+        Dispatcher::new(LogNotifier::new(Vec::new()), planner, start).run_with(
+            &clock,
+            |wait| {
+                waits.push(wait);
+                clock.advance(Duration::from_std(wait).unwrap());
+            },
+            |result| alerts.push(result.unwrap()),
+        );
+        assert_eq!(
+            waits,
+            vec![
+                StdDuration::from_secs(10 * 60),
+                StdDuration::from_secs(60 * 60)
+            ]
+        );
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(clock.now(), start + Duration::minutes(70));
+    }
+}
+
+#[cfg(test)]
+mod reminder_tests {
+    use chrono::{Duration, NaiveTime};
+
+    use crate::{meta::MapMetaKind, reminder::ReminderPlanner, schedule::EventSchedule};
+
+    fn test_event_schedule() -> EventSchedule {
+        EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        }
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_reminder_iter() {
+        let mut reminders = ReminderPlanner::new(
+            vec![test_event_schedule()],
+            vec![Duration::minutes(2), Duration::minutes(10)],
+        )
+        .into_iter();
+
+        let reminder = reminders.next().unwrap();
+        assert_eq!(reminder.time.num_minutes(), 10);
+        assert_eq!(reminder.lead_time.num_minutes(), 10);
+        assert_eq!(reminder.event.start_time.num_minutes(), 20);
+
+        let reminder = reminders.next().unwrap();
+        assert_eq!(reminder.time.num_minutes(), 18);
+        assert_eq!(reminder.lead_time.num_minutes(), 2);
+        assert_eq!(reminder.event.start_time.num_minutes(), 20);
+
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 70);
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 78);
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 130);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_reminder_boundaries() {
+        let planner = || ReminderPlanner::new(
+            vec![test_event_schedule()],
+            vec![Duration::minutes(2), Duration::minutes(10)],
+        );
+
+        // An alarm due exactly at the starting time is given, but only once
+        let mut reminders = planner().into_iter().time(NaiveTime::from_hms_opt(0, 18, 0).unwrap());
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 18);
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 70);
+
+        // Partway through a minute, that minute's alarm has already passed
+        let mut reminders = planner().into_iter().time(NaiveTime::from_hms_opt(0, 18, 30).unwrap());
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 70);
+
+        let mut reminders = planner().into_iter().fast_forward(Duration::minutes(11));
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 18);
+        assert_eq!(reminders.next().unwrap().time.num_minutes(), 70);
+    }
+
+    #[test]
+    fn test_reminder_dedup() {
+        let reminders = ReminderPlanner::new(
+            vec![test_event_schedule(), test_event_schedule()],
+            vec![Duration::minutes(5), Duration::minutes(5)],
+        )
+        .into_iter()
+        .take(3)
+        .map(|reminder| reminder.time.num_minutes())
+        .collect::<Vec<i64>>();
+        assert_eq!(reminders, vec![15, 75, 135]);
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn test_reminder_meta() {
+        let mut reminders = ReminderPlanner::new(
+            MapMetaKind::WorldBosses.info().schedules,
+            vec![Duration::minutes(10)],
+        )
+        .into_iter()
+        .time(NaiveTime::from_hms_opt(0, 5, 0).unwrap());
+        assert_eq!(reminders.next().unwrap().event.schedule.name, "Svanir Shaman Chief");
+        assert_eq!(reminders.next().unwrap().event.schedule.name, "Megadestroyer");
+        assert_eq!(reminders.next().unwrap().event.schedule.name, "Fire Elemental");
+    }
+
+    #[test]
+    fn test_reminder_empty() {
+        let mut reminders =
+            ReminderPlanner::new(Vec::new(), vec![Duration::minutes(10)]).into_iter();
+        assert!(reminders.next().is_none());
+    }
+}
+
+#[cfg(test)]
+mod route_tests {
+    use chrono::Duration;
+
+    use crate::{
+        meta::MapMetaKind,
+        route::{RoutePlanner, Skip, Stop, Target},
+    };
+
+    fn names(stops: &[Stop]) -> Vec<&'static str> {
+        stops.iter().map(|stop| stop.event.schedule.name).collect()
+    }
+
+    #[test]
+    fn test_back_to_back() {
+        // World bosses start every 15 minutes and last 15 minutes
+        let targets = [
+            "Admiral Taidha Covington",
+            "svanir shaman chief",
+            "Megadestroyer",
+        ]
+        .iter()
+        .map(|name| Target::new(MapMetaKind::WorldBosses, name))
+        .collect();
+        let route = RoutePlanner::new(targets).plan(Duration::zero(), Duration::minutes(45));
+        assert_eq!(
+            names(&route.stops),
+            [
+                "Admiral Taidha Covington",
+                "Svanir Shaman Chief",
+                "Megadestroyer"
+            ]
+        );
+        assert_eq!(route.value(), 3);
+        assert!(route.skipped.is_empty());
+        assert!(route.unknown.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_target() {
+        let targets = vec![
+            Target::new(MapMetaKind::WorldBosses, "Megadestroyer"),
+            Target::new(MapMetaKind::WorldBosses, "Megadestoryer"),
+            Target::new(MapMetaKind::AuricBasin, "Megadestroyer"),
+        ];
+        assert!(targets[0].is_known());
+        assert!(!targets[1].is_known());
+        let route = RoutePlanner::new(targets).plan(Duration::zero(), Duration::minutes(45));
+        assert_eq!(names(&route.stops), ["Megadestroyer"]);
+        assert!(route.skipped.is_empty());
+        assert_eq!(route.unknown, [1, 2]);
+    }
+
+    #[test]
+    fn test_travel() {
+        // Five minutes of travel means back to back bosses can't both be attended
+        let targets = [
+            "Admiral Taidha Covington",
+            "Svanir Shaman Chief",
+            "Megadestroyer",
+        ]
+        .iter()
+        .map(|name| Target::new(MapMetaKind::WorldBosses, name).travel(Duration::minutes(5)))
+        .collect();
+        let route = RoutePlanner::new(targets).plan(Duration::minutes(-5), Duration::minutes(45));
+        assert_eq!(
+            names(&route.stops),
+            ["Admiral Taidha Covington", "Megadestroyer"]
+        );
+        assert_eq!(route.stops[1].depart, Duration::minutes(25));
+
+        assert_eq!(route.skipped.len(), 1);
+        let skipped = &route.skipped[0];
+        assert_eq!(skipped.stop.event.schedule.name, "Svanir Shaman Chief");
+        assert_eq!(skipped.reason, Skip::Conflicts(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_value() {
+        // Tequatl at 00:00 is worth more than the two bosses it overlaps
+        let targets = vec![
+            Target::new(MapMetaKind::HardWorldBosses, "Tequatl the Sunless").value(3),
+            Target::new(MapMetaKind::WorldBosses, "Admiral Taidha Covington"),
+            Target::new(MapMetaKind::WorldBosses, "Svanir Shaman Chief"),
+            Target::new(MapMetaKind::WorldBosses, "Megadestroyer"),
+        ];
+        let route = RoutePlanner::new(targets).plan(Duration::zero(), Duration::minutes(45));
+        assert_eq!(
+            names(&route.stops),
+            ["Tequatl the Sunless", "Megadestroyer"]
+        );
+        assert_eq!(route.value(), 4);
+        assert_eq!(
+            route
+                .skipped
+                .iter()
+                .map(|skipped| skipped.reason.clone())
+                .collect::<Vec<Skip>>(),
+            [Skip::Conflicts(vec![0]), Skip::Conflicts(vec![0])]
+        );
+    }
+
+    #[test]
+    fn test_outside_window() {
+        let targets = vec![
+            Target::new(MapMetaKind::WorldBosses, "Admiral Taidha Covington")
+                .travel(Duration::minutes(10)),
+            Target::new(MapMetaKind::HardWorldBosses, "Triple Trouble"),
+        ];
+        let route = RoutePlanner::new(targets).plan(Duration::zero(), Duration::minutes(75));
+        assert!(route.stops.is_empty());
+        assert_eq!(route.skipped.len(), 2);
+        assert!(route
+            .skipped
+            .iter()
+            .all(|skipped| skipped.reason == Skip::OutsideWindow));
+    }
+
+    #[test]
+    fn test_optimal() {
+        let targets = vec![
+            Target::new(MapMetaKind::WorldBosses, "Svanir Shaman Chief").value(2),
+            Target::new(MapMetaKind::WorldBosses, "Fire Elemental").travel(Duration::minutes(10)),
+            Target::new(MapMetaKind::AuricBasin, "Octovine").value(3),
+            Target::new(MapMetaKind::TangledDepths, "Chak Gerent").value(4),
+            Target::new(MapMetaKind::DragonsEnd, "Jade Maw").travel(Duration::minutes(3)),
+            Target::new(MapMetaKind::HardWorldBosses, "Karka Queen").value(5),
+        ];
+        let planner = RoutePlanner::new(targets);
+        let (start, end) = (Duration::zero(), Duration::hours(4));
+        let route = planner.plan(start, end);
+
+        // Try every combination of the stops that fit in the window
+        let mut candidates = route
+            .stops
+            .iter()
+            .chain(
+                route
+                    .skipped
+                    .iter()
+                    .filter(|skipped| skipped.reason != Skip::OutsideWindow)
+                    .map(|skipped| &skipped.stop),
+            )
+            .collect::<Vec<&Stop>>();
+        candidates.sort_by_key(|stop| stop.depart);
+        assert!(candidates.len() <= 20);
+        let best = (0u32..1 << candidates.len())
+            .filter_map(|set| {
+                let chosen = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| set & (1 << i) != 0)
+                    .map(|(_, stop)| *stop)
+                    .collect::<Vec<&Stop>>();
+                let fits = chosen
+                    .windows(2)
+                    .all(|pair| pair[0].end_time() <= pair[1].depart);
+                fits.then(|| chosen.iter().map(|stop| stop.value as u64).sum::<u64>())
+            })
+            .max()
+            .unwrap();
+        assert_eq!(route.value(), best);
+
+        for pair in route.stops.windows(2) {
+            assert!(pair[0].end_time() <= pair[1].depart);
+        }
+        assert!(route
+            .stops
+            .iter()
+            .all(|stop| stop.depart >= start && stop.end_time() <= end));
+    }
+}
+
+#[cfg(all(test, feature = "tokio"))]
+mod stream_tests {
+    use chrono::{Duration, NaiveTime};
+    use futures::StreamExt;
+    use tokio::time::Instant;
+
+    use crate::{meta::MapMetaKind, schedule::EventSchedule, stream::Notification};
+
+    #[tokio::test(start_paused = true)]
+    async fn test_event_stream() {
+        let test_event_schedule = EventSchedule {
+            name: "Reoccurring event",
+            offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+            frequency: Duration::hours(1),
+            length: Duration::minutes(15),
+        };
+        let start = Instant::now();
+        let mut stream = test_event_schedule
+            .into_iter()
+            .time(NaiveTime::from_hms_opt(0, 10, 0).unwrap())
+            .into_stream();
+
+        match stream.next().await.unwrap() {
+            Notification::Started(event) => assert_eq!(event.start_time.num_minutes(), 20),
+            Notification::Ended(_) => panic!("expected event to start"),
+        }
+        assert_eq!(start.elapsed().as_secs(), 10 * 60);
+
+        stream.next().await.unwrap();
+        assert_eq!(start.elapsed().as_secs(), 70 * 60);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_event_stream_endings() {
+        let start = Instant::now();
+        let mut stream = MapMetaKind::LeyLineAnomaly
+            .into_iter()
+            .time(NaiveTime::from_hms_opt(0, 0, 0).unwrap())
+            .into_stream()
+            .with_endings();
+
+        match stream.next().await.unwrap() {
+            Notification::Started(event) => assert_eq!(event.start_time.num_minutes(), 20),
+            Notification::Ended(_) => panic!("expected event to start"),
+        }
+        assert_eq!(start.elapsed().as_secs(), 20 * 60);
+
+        match stream.next().await.unwrap() {
+            Notification::Ended(event) => assert_eq!(event.start_time.num_minutes(), 20),
+            Notification::Started(_) => panic!("expected event to end"),
+        }
+        assert_eq!(start.elapsed().as_secs(), 40 * 60);
+
+        match stream.next().await.unwrap() {
+            Notification::Started(event) => assert_eq!(event.start_time.num_minutes(), 140),
+            Notification::Ended(_) => panic!("expected event to start"),
+        }
+        assert_eq!(start.elapsed().as_secs(), 140 * 60);
+    }
+}
+
+#[cfg(all(test, feature = "server"))]
+mod server_tests {
+    use axum::{
+        body::{to_bytes, Body},
+        http::{Request, StatusCode},
+    };
+    use chrono::{TimeZone, Utc};
+    use serde_json::Value;
+    use tower::ServiceExt;
+
+    use crate::{clock::FixedClock, server::router};
+
+    async fn get(uri: &str) -> (StatusCode, Value) {
+        let clock = FixedClock::new(Utc.with_ymd_and_hms(2022, 3, 1, 8, 41, 0).unwrap());
+        let response = router(clock)
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_metas() {
+        let (status, metas) = get("/metas").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(metas.as_array().unwrap().len(), 28);
+        assert_eq!(metas[1]["id"], "WorldBosses");
+        assert_eq!(metas[1]["category"], "Core Tyria");
+        assert_eq!(metas[1]["schedules"][1]["name"], "Svanir Shaman Chief");
+        assert_eq!(metas[1]["schedules"][1]["offset"], "00:15");
+        assert_eq!(metas[1]["schedules"][1]["frequency_minutes"], 120);
+    }
+
+    #[tokio::test]
+    async fn test_active() {
+        let (status, events) = get("/active?meta=WorldBosses").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(events[0]["name"], "Claw of Jormag");
+        assert_eq!(events[0]["end"], "2022-03-01T08:45:00Z");
+
+        let (_, events) = get("/active?meta=LeyLineAnomaly&at=2022-03-01T08:50:00Z").await;
+        assert_eq!(events.as_array().unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_upcoming() {
+        let (status, events) = get("/upcoming?meta=world-bosses,auric-basin&limit=3").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(events.as_array().unwrap().len(), 3);
+        assert_eq!(events[0]["start"], "2022-03-01T08:45:00Z");
+        assert_eq!(events[2]["name"], "Admiral Taidha Covington");
+
+        let (status, events) = get("/upcoming?category=EndOfDragons&limit=50").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(events
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|event| event["category"] == "End of Dragons"));
+
+        let (status, error) = get("/upcoming?meta=nowhere").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(error["error"], "unknown map meta: 'nowhere'");
+    }
+
+    #[tokio::test]
+    async fn test_timetable() {
+        let (status, events) = get("/timetable?date=2022-03-02&meta=HardWorldBosses").await;
+        assert_eq!(status, StatusCode::OK);
+        let events = events.as_array().unwrap();
+        assert_eq!(events.len(), 18);
+        assert_eq!(events[0]["start"], "2022-03-02T00:00:00Z");
+        assert_eq!(events[0]["name"], "Tequatl the Sunless");
+    }
+}
+
+#[cfg(all(test, feature = "wasm", target_arch = "wasm32"))]
+mod wasm_tests {
+    use js_sys::{Array, Date, Reflect};
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use crate::wasm::{active, metas, upcoming};
+
+    fn get(object: &JsValue, key: &str) -> JsValue {
+        Reflect::get(object, &JsValue::from_str(key)).unwrap()
+    }
+
+    fn test_date() -> Date {
+        Date::new(&JsValue::from_str("2022-03-01T08:41:00Z"))
+    }
+
+    fn world_bosses() -> Option<Vec<String>> {
+        Some(vec![String::from("WorldBosses")])
+    }
+
+    #[wasm_bindgen_test]
+    fn test_metas() {
+        let metas = metas();
+        assert_eq!(metas.length(), 28);
+        assert_eq!(get(&metas.get(1), "id"), "WorldBosses");
+        assert_eq!(get(&metas.get(1), "category"), "Core Tyria");
+        let schedules = Array::from(&get(&metas.get(1), "schedules"));
+        assert_eq!(get(&schedules.get(0), "frequencyMinutes"), 180.0);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_active() {
+        let events = active(Some(test_date()), world_bosses()).unwrap();
+        assert_eq!(events.length(), 1);
+        assert_eq!(get(&events.get(0), "name"), "Claw of Jormag");
+        let end = Date::from(get(&events.get(0), "end"));
+        assert_eq!(end.to_iso_string(), "2022-03-01T08:45:00.000Z");
+
+        assert!(active(None, Some(vec![String::from("nowhere")])).is_err());
+        let invalid = Date::new(&JsValue::from_str("not a date"));
+        assert!(active(Some(invalid), world_bosses()).is_err());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_upcoming() {
+        let events = upcoming(Some(test_date()), Some(3), world_bosses()).unwrap();
+        assert_eq!(events.length(), 3);
+        assert_eq!(get(&events.get(0), "name"), "Fire Elemental");
+        let start = Date::from(get(&events.get(0), "start"));
+        assert_eq!(start.to_iso_string(), "2022-03-01T08:45:00.000Z");
+
+        assert_eq!(upcoming(None, None, None).unwrap().length(), 10);
+    }
+}
+
+#[cfg(test)]
+mod validate_tests {
+    use alloc::borrow::Cow;
+
+    use chrono::{Duration, NaiveTime};
+
+    use crate::{
+        category::Category,
+        meta::{MapMeta, MapMetaKind},
+        schedule::EventSchedule,
+        validate::{validate, Expectations, Lint, Severity},
+    };
+
+    fn schedule(
+        name: &'static str,
+        offset: (u32, u32),
+        frequency: i64,
+        length: i64,
+    ) -> EventSchedule {
+        EventSchedule {
+            name,
+            offset: NaiveTime::from_hms_opt(offset.0, offset.1, 0).unwrap(),
+            frequency: Duration::minutes(frequency),
+            length: Duration::minutes(length),
+        }
+    }
+
+    fn meta(schedules: Vec<EventSchedule>) -> MapMeta {
+        MapMeta {
+            name: "Test",
+            category: Category::CoreTyria,
+            schedules: Cow::Owned(schedules),
+        }
+    }
+
+    #[test]
+    fn test_built_in_metas() {
+        for kind in MapMetaKind::all_keys() {
+            let diagnostics = validate(&kind.info(), Expectations::of(kind));
+            assert!(diagnostics.is_empty(), "{:#?}", diagnostics);
+        }
+    }
+
+    #[test]
+    fn test_schedule_lints() {
+        let diagnostics = validate(
+            &meta(vec![
+                schedule("Never", (0, 0), 0, 10),
+                schedule("Instant", (0, 0), 60, 0),
+                schedule("Long", (0, 0), 60, 90),
+                schedule("Late", (10, 0), 120, 20),
+                schedule("Uneven", (0, 5), 7 * 60, 20),
+                schedule("Late", (10, 0), 120, 20),
+            ]),
+            Expectations {
+                continuous: false,
+                overlapping: true,
+            },
+        );
+        let lints = diagnostics
+            .iter()
+            .map(|d| d.lint.clone())
+            .collect::<Vec<Lint>>();
+        assert_eq!(
+            lints,
+            [
+                Lint::InvalidFrequency { schedule: 0 },
+                Lint::InvalidLength { schedule: 1 },
+                Lint::LengthExceedsFrequency { schedule: 2 },
+                Lint::OffsetBeyondPeriod { schedule: 3 },
+                Lint::UnevenFrequency { schedule: 4 },
+                Lint::Duplicate {
+                    schedule: 5,
+                    original: 3
+                },
+                Lint::OffsetBeyondPeriod { schedule: 5 },
+            ]
+        );
+        assert_eq!(diagnostics[0].severity(), Severity::Error);
+        assert_eq!(diagnostics[3].severity(), Severity::Warning);
+        assert_eq!(
+            diagnostics[3].to_string(),
+            "Test: warning: Late first starts at 10:00 but starts every 2h 00m"
+        );
+    }
+
+    #[test]
+    fn test_error_lints() {
+        let with = |frequency: Duration, length: Duration, offset: NaiveTime| EventSchedule {
+            name: "Event",
+            offset,
+            frequency,
+            length,
+        };
+        let midnight = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        let hour = Duration::hours(1);
+        let cases = [
+            (
+                with(Duration::seconds(90), hour, midnight),
+                Lint::InvalidFrequency { schedule: 0 },
+            ),
+            (
+                with(Duration::seconds(30), hour, midnight),
+                Lint::InvalidFrequency { schedule: 0 },
+            ),
+            (
+                with(hour, Duration::seconds(30), midnight),
+                Lint::InvalidLength { schedule: 0 },
+            ),
+            (
+                with(
+                    Duration::weeks(5),
+                    Duration::weeks(4) + Duration::minutes(1),
+                    midnight,
+                ),
+                Lint::LengthTooLong { schedule: 0 },
+            ),
+            (
+                with(hour, hour, NaiveTime::from_hms_opt(0, 0, 30).unwrap()),
+                Lint::InvalidOffset { schedule: 0 },
+            ),
+        ];
+        for (schedule, lint) in cases {
+            let diagnostics = validate(
+                &meta(vec![schedule.clone()]),
+                Expectations {
+                    continuous: false,
+                    overlapping: true,
+                },
+            );
+            let error = MapMeta::new("Test", Category::CoreTyria, vec![schedule]).err();
+            assert_eq!(diagnostics[0].lint, lint);
+            assert_eq!(diagnostics[0].severity(), Severity::Error);
+            assert_eq!(
+                Some(diagnostics[0].message.clone()),
+                error.map(|e| e.to_string())
+            );
+        }
+
+        let diagnostics = validate(&meta(Vec::new()), Expectations::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].lint, Lint::NoSchedules);
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "Test: error: map meta has no schedules"
+        );
+    }
+
+    #[test]
+    fn test_overlaps() {
+        let expectations = Expectations::default();
+        let diagnostics = validate(
+            &meta(vec![
+                schedule("First", (0, 0), 120, 30),
+                schedule("Second", (0, 20), 120, 30),
+                schedule("Third", (1, 0), 120, 10),
+                // Ends across the end of the period, when First starts again
+                schedule("Fourth", (1, 45), 120, 20),
+            ]),
+            expectations,
+        );
+        let lints = diagnostics
+            .iter()
+            .map(|d| d.lint.clone())
+            .collect::<Vec<Lint>>();
+        assert_eq!(
+            lints,
+            [
+                Lint::Overlap {
+                    first: 0,
+                    second: 1
+                },
+                Lint::Overlap {
+                    first: 0,
+                    second: 3
+                },
+            ]
+        );
+        assert_eq!(diagnostics[1].message, "First overlaps Fourth");
+    }
+
+    #[test]
+    fn test_gaps() {
+        let expectations = Expectations {
+            continuous: true,
+            overlapping: false,
+        };
+        let diagnostics = validate(
+            &meta(vec![
+                schedule("Morning", (0, 10), 120, 50),
+                schedule("Evening", (1, 10), 120, 40),
+            ]),
+            expectations,
+        );
+        let lints = diagnostics
+            .iter()
+            .map(|d| d.lint.clone())
+            .collect::<Vec<Lint>>();
+        assert_eq!(
+            lints,
+            [
+                Lint::Gap {
+                    start: Duration::minutes(-10),
+                    end: Duration::minutes(10)
+                },
+                Lint::Gap {
+                    start: Duration::minutes(60),
+                    end: Duration::minutes(70)
+                },
+            ]
+        );
+        assert_eq!(
+            diagnostics[0].message,
+            "no event is active from 23:50 to 00:10"
+        );
+
+        let continuous = meta(vec![
+            schedule("Morning", (0, 10), 120, 60),
+            schedule("Evening", (1, 10), 120, 60),
+        ]);
+        assert!(validate(&continuous, expectations).is_empty());
+    }
+}
+
 #[cfg(test)]
 mod readme_tests {
     use chrono::{Duration, NaiveTime};
@@ -236,7 +2805,7 @@ mod readme_tests {
                 .take(5)
                 .collect::<Vec<EventInstance>>();
 
-        assert_eq!(next_5_auricbasin_events.get(0).unwrap().schedule.name, "Challenges");
+        assert_eq!(next_5_auricbasin_events.first().unwrap().schedule.name, "Challenges");
         assert_eq!(next_5_auricbasin_events.get(1).unwrap().schedule.name, "Octovine");
         assert_eq!(next_5_auricbasin_events.get(2).unwrap().schedule.name, "Reset");
         assert_eq!(next_5_auricbasin_events.get(3).unwrap().schedule.name, "Pylons");
@@ -249,7 +2818,7 @@ mod readme_tests {
         let mut tangled_depths_5am_utc =
             MapMetaKind::TangledDepths
                 .into_iter()
-                .time(NaiveTime::from_hms(5, 0, 0));
+                .time(NaiveTime::from_hms_opt(5, 0, 0).unwrap());
 
         assert_eq!(tangled_depths_5am_utc.next().unwrap().schedule.name, "Prep");
         assert_eq!(tangled_depths_5am_utc.next().unwrap().schedule.name, "Chak Gerent");
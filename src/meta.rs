@@ -1,81 +1,55 @@
-use std::ops::Add;
+use core::{fmt, str::FromStr};
 
-use chrono::{Duration, NaiveTime, Timelike};
+use alloc::{borrow::Cow, format, string::String};
 
-use crate::schedule::EventSchedule;
+use chrono::{Duration, NaiveTime};
+
+use crate::{
+    clock::Clock, cursor::Cursor, cycle::CycleTable, error::Error, schedule::EventSchedule,
+};
 
 use super::{category::Category, event::EventInstance};
 
 pub struct MapMeta {
     pub name: &'static str,
     pub category: Category,
-    pub schedules: Vec<EventSchedule>,
-}
-
-impl IntoIterator for MapMeta {
-    type Item = EventInstance;
 
-    type IntoIter = IntoIter;
+    /// The schedules of every event in the meta, borrowed from static tables for built-in metas
+    pub schedules: Cow<'static, [EventSchedule]>,
+}
 
-    fn into_iter(self) -> Self::IntoIter {
-        IntoIter {
-            current_time: Duration::zero(),
-            schedules: self.schedules,
+impl MapMeta {
+    /// Create a map meta, checking every schedule can be iterated
+    pub fn new(
+        name: &'static str,
+        category: Category,
+        schedules: impl Into<Cow<'static, [EventSchedule]>>,
+    ) -> Result<Self, Error> {
+        let schedules = schedules.into();
+        if schedules.is_empty() {
+            return Err(Error::NoSchedules);
         }
+        schedules.iter().try_for_each(EventSchedule::check)?;
+        Ok(MapMeta {
+            name,
+            category,
+            schedules,
+        })
     }
 }
 
-pub struct IntoIter {
-    /// Respresents the amount of time from UTC 00:00
-    current_time: Duration,
-
-    /// A meta's schedule is a collection of Event's (in that meta) schedules
-    schedules: Vec<EventSchedule>,
-}
-
-impl IntoIter {
-    /// Skip to a certain time of day
-    pub fn time(mut self, time: NaiveTime) -> Self {
-        self.current_time = Duration::seconds(time.num_seconds_from_midnight() as i64);
-        self
-    }
+impl IntoIterator for MapMeta {
+    type Item = EventInstance;
 
-    /// Skip forward an amount of time
-    pub fn fast_forward(mut self, amount: Duration) -> Self {
-        self.current_time = self.current_time.add(amount);
-        self
-    }
+    type IntoIter = IntoIter;
 
-    pub fn now(&self) -> Option<EventInstance> {
-        let time = self.current_time;
-        self.schedules
-            .iter()
-            .find_map(|event_schedules| event_schedules.iter().fast_forward(time).now())
+    fn into_iter(self) -> Self::IntoIter {
+        Cursor::new(Cow::Owned(CycleTable::new(self.schedules)))
     }
 }
 
-impl Iterator for IntoIter {
-    type Item = EventInstance;
-
-    fn next(&mut self) -> Option<EventInstance> {
-        let next_event: EventInstance = self
-            .schedules
-            .iter()
-            .filter_map(|event_schedule| {
-                event_schedule.iter().fast_forward(self.current_time).next()
-            })
-            .reduce(|event_a, event_b| {
-                if event_b.start_time < event_a.start_time {
-                    event_b
-                } else {
-                    event_a
-                }
-            })
-            .unwrap();
-        self.current_time = next_event.start_time;
-        Some(next_event)
-    }
-}
+/// An iterator over the events of a [MapMeta] or [MapMetaKind]
+pub type IntoIter = Cursor;
 
 impl IntoIterator for MapMetaKind {
     type Item = EventInstance;
@@ -83,30 +57,35 @@ impl IntoIterator for MapMetaKind {
     type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.info().into_iter()
+        Cursor::new(self.cycle_table())
     }
 }
 
-impl<'a> IntoIterator for &'a MapMetaKind {
+impl IntoIterator for &MapMetaKind {
     type Item = EventInstance;
 
     type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.info().into_iter()
+        Cursor::new(self.cycle_table())
     }
 }
 
-impl<'a> IntoIterator for &'a mut MapMetaKind {
+impl IntoIterator for &mut MapMetaKind {
     type Item = EventInstance;
 
     type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.info().into_iter()
+        Cursor::new(self.cycle_table())
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "python",
+    pyo3::pyclass(module = "gw2timers", eq, eq_int, hash, frozen)
+)]
 pub enum MapMetaKind {
     DayAndNight,
     WorldBosses,
@@ -173,721 +152,840 @@ impl MapMetaKind {
         ]
     }
 
+    /// Get every occurrence of this map meta's events over the period they repeat in
+    ///
+    /// With the `std` feature enabled, the tables of every map meta are built once and shared.
+    pub fn cycle_table(&self) -> Cow<'static, CycleTable> {
+        #[cfg(feature = "std")]
+        {
+            static TABLES: std::sync::OnceLock<Vec<CycleTable>> = std::sync::OnceLock::new();
+            let tables = TABLES.get_or_init(|| {
+                MapMetaKind::all_keys()
+                    .iter()
+                    .map(|kind| CycleTable::new(kind.info().schedules))
+                    .collect()
+            });
+            Cow::Borrowed(&tables[*self as usize])
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            Cow::Owned(CycleTable::new(self.info().schedules))
+        }
+    }
+
+    /// Iterate this map meta starting at the current time of `clock`
+    pub fn iter_from(&self, clock: &impl Clock) -> IntoIter {
+        self.into_iter().time(clock.now().time())
+    }
+
     /// Get the schedule of this map meta event
     pub fn info(&self) -> MapMeta {
         match self {
             MapMetaKind::DayAndNight => MapMeta {
                 name: "Day and Night",
                 category: Category::CoreTyria,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Dawn",
-                        offset: NaiveTime::from_hms(0, 25, 0),
-                        length: Duration::minutes(5),
-                        frequency: Duration::hours(2),
-                    },
-                    EventSchedule {
-                        name: "Day",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        length: Duration::minutes(70),
-                        frequency: Duration::hours(2),
-                    },
-                    EventSchedule {
-                        name: "Dusk",
-                        offset: NaiveTime::from_hms(1, 40, 0),
-                        length: Duration::minutes(5),
-                        frequency: Duration::hours(2),
-                    },
-                    EventSchedule {
-                        name: "Night",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        length: Duration::minutes(40),
-                        frequency: Duration::hours(2),
-                    },
-                ],
+                schedules: Cow::Borrowed(&DAY_AND_NIGHT),
             },
             MapMetaKind::WorldBosses => MapMeta {
                 name: "World Bosses",
                 category: Category::CoreTyria,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Admiral Taidha Covington",
-                        offset: NaiveTime::from_hms(0, 0, 0),
-                        frequency: Duration::hours(3),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Svanir Shaman Chief",
-                        offset: NaiveTime::from_hms(0, 15, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Megadestroyer",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(3),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Fire Elemental",
-                        offset: NaiveTime::from_hms(0, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "The Shatterer",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(3),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Great Jungle Wurm",
-                        offset: NaiveTime::from_hms(1, 15, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Modniir Ulgoth",
-                        offset: NaiveTime::from_hms(1, 30, 0),
-                        frequency: Duration::hours(3),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Shadow Behemoth",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Golem Mark II",
-                        offset: NaiveTime::from_hms(2, 0, 0),
-                        frequency: Duration::hours(3),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Claw of Jormag",
-                        offset: NaiveTime::from_hms(2, 30, 0),
-                        frequency: Duration::hours(3),
-                        length: Duration::minutes(15),
-                    },
-                ],
+                schedules: Cow::Borrowed(&WORLD_BOSSES),
             },
             MapMetaKind::HardWorldBosses => MapMeta {
                 name: "Hard World Bosses",
                 category: Category::CoreTyria,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Tequatl the Sunless",
-                        offset: NaiveTime::from_hms(0, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Triple Trouble",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Karka Queen",
-                        offset: NaiveTime::from_hms(2, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Tequatl the Sunless",
-                        offset: NaiveTime::from_hms(3, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Triple Trouble",
-                        offset: NaiveTime::from_hms(4, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Karka Queen",
-                        offset: NaiveTime::from_hms(6, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Tequatl the Sunless",
-                        offset: NaiveTime::from_hms(7, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Triple Trouble",
-                        offset: NaiveTime::from_hms(8, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Karka Queen",
-                        offset: NaiveTime::from_hms(10, 30, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Tequatl the Sunless",
-                        offset: NaiveTime::from_hms(11, 30, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Triple Trouble",
-                        offset: NaiveTime::from_hms(12, 30, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Karka Queen",
-                        offset: NaiveTime::from_hms(15, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Tequatl the Sunless",
-                        offset: NaiveTime::from_hms(16, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Triple Trouble",
-                        offset: NaiveTime::from_hms(17, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Karka Queen",
-                        offset: NaiveTime::from_hms(18, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Tequatl the Sunless",
-                        offset: NaiveTime::from_hms(19, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Triple Trouble",
-                        offset: NaiveTime::from_hms(20, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Karka Queen",
-                        offset: NaiveTime::from_hms(23, 0, 0),
-                        frequency: Duration::hours(24),
-                        length: Duration::minutes(30),
-                    },
-                ],
+                schedules: Cow::Borrowed(&HARD_WORLD_BOSSES),
             },
             MapMetaKind::LeyLineAnomaly => MapMeta {
                 name: "Ley-Line Anomaly",
                 category: Category::CoreTyria,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Timberline Falls",
-                        offset: NaiveTime::from_hms(0, 20, 0),
-                        frequency: Duration::hours(6),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Iron Marches",
-                        offset: NaiveTime::from_hms(2, 20, 0),
-                        frequency: Duration::hours(6),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Gendarran Fields",
-                        offset: NaiveTime::from_hms(4, 20, 0),
-                        frequency: Duration::hours(6),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&LEY_LINE_ANOMALY),
             },
             MapMetaKind::TwistedMarionette => MapMeta {
                 name: "Twisted Marionette",
                 category: Category::CoreTyria,
-                schedules: vec![EventSchedule {
-                    name: "Twisted Marionette (Public)",
-                    offset: NaiveTime::from_hms(0, 0, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::minutes(20),
-                }],
+                schedules: Cow::Borrowed(&TWISTED_MARIONETTE),
             },
             MapMetaKind::PVPTournaments => MapMeta {
                 name: "PvP Tournaments",
                 category: Category::CoreTyria,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Balthazar's Brawl",
-                        offset: NaiveTime::from_hms(0, 0, 0),
-                        frequency: Duration::hours(12),
-                        length: Duration::hours(1),
-                    },
-                    EventSchedule {
-                        name: "Grenth's Game",
-                        offset: NaiveTime::from_hms(3, 0, 0),
-                        frequency: Duration::hours(12),
-                        length: Duration::hours(1),
-                    },
-                    EventSchedule {
-                        name: "Melandru's Matchup",
-                        offset: NaiveTime::from_hms(6, 0, 0),
-                        frequency: Duration::hours(12),
-                        length: Duration::hours(1),
-                    },
-                    EventSchedule {
-                        name: "Lyssa's Legions",
-                        offset: NaiveTime::from_hms(9, 0, 0),
-                        frequency: Duration::hours(12),
-                        length: Duration::hours(1),
-                    },
-                ],
+                schedules: Cow::Borrowed(&PVP_TOURNAMENTS),
             },
             MapMetaKind::DryTop => MapMeta {
                 name: "Dry Top",
                 category: Category::LivingWorldSeason2,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Crash Site",
-                        offset: NaiveTime::from_hms(0, 0, 0),
-                        frequency: Duration::hours(1),
-                        length: Duration::minutes(40),
-                    },
-                    EventSchedule {
-                        name: "Sandstorm",
-                        offset: NaiveTime::from_hms(0, 40, 0),
-                        frequency: Duration::hours(1),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&DRY_TOP),
             },
             MapMetaKind::VerdantBrink => MapMeta {
                 name: "Verdant Brink",
                 category: Category::HeartOfThorns,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Night: Night and the Enemy",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(25),
-                    },
-                    EventSchedule {
-                        name: "Night Bosses",
-                        offset: NaiveTime::from_hms(10, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Day: Securing Verdant Brink",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::hours(1).add(Duration::minutes(15)),
-                    },
-                ],
+                schedules: Cow::Borrowed(&VERDANT_BRINK),
             },
             MapMetaKind::AuricBasin => MapMeta {
                 name: "Auric Basin",
                 category: Category::HeartOfThorns,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Challenges",
-                        offset: NaiveTime::from_hms(0, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Octovine",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Reset",
-                        offset: NaiveTime::from_hms(1, 20, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(10),
-                    },
-                    EventSchedule {
-                        name: "Pylons",
-                        offset: NaiveTime::from_hms(1, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(75),
-                    },
-                ],
+                schedules: Cow::Borrowed(&AURIC_BASIN),
             },
             MapMetaKind::TangledDepths => MapMeta {
                 name: "Tangled Depths",
                 category: Category::HeartOfThorns,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Prep",
-                        offset: NaiveTime::from_hms(0, 25, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(5),
-                    },
-                    EventSchedule {
-                        name: "Chak Gerent",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Help the Outposts",
-                        offset: NaiveTime::from_hms(0, 50, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::hours(1).add(Duration::minutes(35)),
-                    },
-                ],
+                schedules: Cow::Borrowed(&TANGLED_DEPTHS),
             },
             MapMetaKind::DragonsStand => MapMeta {
                 name: "Dragon's Stand",
                 category: Category::HeartOfThorns,
-                schedules: vec![EventSchedule {
-                    name: "Start advancing on the Blighting Towers",
-                    offset: NaiveTime::from_hms(1, 30, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::hours(2),
-                }],
+                schedules: Cow::Borrowed(&DRAGONS_STAND),
             },
             MapMetaKind::LakeDoric => MapMeta {
                 name: "Lake Doric",
                 category: Category::LivingWorldSeason3,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Noran's Homestead",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Saidra's Haven",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(45),
-                    },
-                    EventSchedule {
-                        name: "New Loamhurst",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(45),
-                    },
-                ],
+                schedules: Cow::Borrowed(&LAKE_DORIC),
             },
             MapMetaKind::CrystalOasis => MapMeta {
                 name: "Crystal Oasis",
                 category: Category::PathOfFire,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Rounds 1 to 3",
-                        offset: NaiveTime::from_hms(0, 5, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(10),
-                    },
-                    EventSchedule {
-                        name: "Pinata/Reset",
-                        offset: NaiveTime::from_hms(0, 20, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(10),
-                    },
-                ],
+                schedules: Cow::Borrowed(&CRYSTAL_OASIS),
             },
             MapMetaKind::DesertHighlands => MapMeta {
                 name: "Desert Highlands",
                 category: Category::PathOfFire,
-                schedules: vec![EventSchedule {
-                    name: "Buried Treasure",
-                    offset: NaiveTime::from_hms(1, 0, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::minutes(20),
-                }],
+                schedules: Cow::Borrowed(&DESERT_HIGHLANDS),
             },
             MapMetaKind::ElonRiverlands => MapMeta {
                 name: "Elon Riverlands",
                 category: Category::PathOfFire,
-                schedules: vec![
-                    EventSchedule {
-                        name: "The Path to Ascension: Augury Rock",
-                        offset: NaiveTime::from_hms(1, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(25),
-                    },
-                    EventSchedule {
-                        name: "Doppelganger",
-                        offset: NaiveTime::from_hms(1, 50, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&ELON_RIVERLANDS),
             },
             MapMetaKind::TheDesolation => MapMeta {
                 name: "The Desolation",
                 category: Category::PathOfFire,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Junudu Rising",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Maws of Torment",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Junudu Rising",
-                        offset: NaiveTime::from_hms(1, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&THE_DESOLATION),
             },
             MapMetaKind::DomainOfVabbi => MapMeta {
                 name: "Domain of Vabbi",
                 category: Category::PathOfFire,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Forged with Fire",
-                        offset: NaiveTime::from_hms(0, 0, 0),
-                        frequency: Duration::hours(1),
-                        length: Duration::minutes(30),
-                    },
-                    EventSchedule {
-                        name: "Serpents' Ire",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(30),
-                    },
-                ],
+                schedules: Cow::Borrowed(&DOMAIN_OF_VABBI),
             },
             MapMetaKind::DomainOfIstan => MapMeta {
                 name: "Domain of Istan",
                 category: Category::LivingWorldSeason4,
-                schedules: vec![EventSchedule {
-                    name: "Palawadan",
-                    offset: NaiveTime::from_hms(1, 45, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::minutes(30),
-                }],
+                schedules: Cow::Borrowed(&DOMAIN_OF_ISTAN),
             },
             MapMetaKind::JahaiBluffs => MapMeta {
                 name: "Jahai Bluffs",
                 category: Category::LivingWorldSeason4,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Escorts",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Death-Branded Shatterer",
-                        offset: NaiveTime::from_hms(1, 15, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                ],
+                schedules: Cow::Borrowed(&JAHAI_BLUFFS),
             },
             MapMetaKind::ThunderheadPeaks => MapMeta {
                 name: "Thunderhead Peaks",
                 category: Category::LivingWorldSeason4,
-                schedules: vec![
-                    EventSchedule {
-                        name: "The Oil Floes",
-                        offset: NaiveTime::from_hms(0, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Thunderhead Keep",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&THUNDERHEAD_PEAKS),
             },
             MapMetaKind::GrothmarValley => MapMeta {
                 name: "Grothmar Valley",
                 category: Category::TheIcebroodSaga,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Effigy",
-                        offset: NaiveTime::from_hms(0, 10, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Doomlore Shrine",
-                        offset: NaiveTime::from_hms(0, 38, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(22),
-                    },
-                    EventSchedule {
-                        name: "Ooze Pits",
-                        offset: NaiveTime::from_hms(1, 5, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                    EventSchedule {
-                        name: "Metal Concert",
-                        offset: NaiveTime::from_hms(1, 40, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&GROTHMAR_VALLEY),
             },
             MapMetaKind::BjoraMarches => MapMeta {
                 name: "Bjora Marches",
                 category: Category::TheIcebroodSaga,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Shards and Construct",
-                        offset: NaiveTime::from_hms(0, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(5),
-                    },
-                    EventSchedule {
-                        name: "Icebrood Champions",
-                        offset: NaiveTime::from_hms(0, 5, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                    EventSchedule {
-                        name: "Drakkar and Spirits of the Wild",
-                        offset: NaiveTime::from_hms(1, 5, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(35),
-                    },
-                    EventSchedule {
-                        name: "Raven Shrines",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(15),
-                    },
-                ],
+                schedules: Cow::Borrowed(&BJORA_MARCHES),
             },
             MapMetaKind::Dragonstorm => MapMeta {
                 name: "Dragonstorm",
                 category: Category::TheIcebroodSaga,
-                schedules: vec![EventSchedule {
-                    name: "Dragonstorm (Public)",
-                    offset: NaiveTime::from_hms(1, 0, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::minutes(20),
-                }],
+                schedules: Cow::Borrowed(&DRAGONSTORM),
             },
             MapMetaKind::Cantha => MapMeta {
                 name: "Cantha: Day and Night",
                 category: Category::EndOfDragons,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Dawn",
-                        offset: NaiveTime::from_hms(0, 25, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(5),
-                    },
-                    EventSchedule {
-                        name: "Day",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::hours(1).add(Duration::minutes(10)),
-                    },
-                    EventSchedule {
-                        name: "Dusk",
-                        offset: NaiveTime::from_hms(1, 40, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(5),
-                    },
-                    EventSchedule {
-                        name: "Night",
-                        offset: NaiveTime::from_hms(1, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(40),
-                    },
-                ],
+                schedules: Cow::Borrowed(&CANTHA),
             },
             MapMetaKind::SeitungProvince => MapMeta {
                 name: "Seitung Province",
                 category: Category::EndOfDragons,
-                schedules: vec![EventSchedule {
-                    name: "Aetherblade Assault",
-                    offset: NaiveTime::from_hms(1, 30, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::minutes(30),
-                }],
+                schedules: Cow::Borrowed(&SEITUNG_PROVINCE),
             },
             MapMetaKind::NewKainengCity => MapMeta {
                 name: "New Kaineng City",
                 category: Category::EndOfDragons,
-                schedules: vec![EventSchedule {
-                    name: "Kaineng Blackout",
-                    offset: NaiveTime::from_hms(0, 0, 0),
-                    frequency: Duration::hours(2),
-                    length: Duration::minutes(40),
-                }],
+                schedules: Cow::Borrowed(&NEW_KAINENG_CITY),
             },
             MapMetaKind::TheEchovaldWilds => MapMeta {
                 name: "The Echovald Wilds",
                 category: Category::EndOfDragons,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Gang War",
-                        offset: NaiveTime::from_hms(0, 30, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(35),
-                    },
-                    EventSchedule {
-                        name: "Aspenwood",
-                        offset: NaiveTime::from_hms(1, 40, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(20),
-                    },
-                ],
+                schedules: Cow::Borrowed(&THE_ECHOVALD_WILDS),
             },
             MapMetaKind::DragonsEnd => MapMeta {
                 name: "Dragon's End",
                 category: Category::EndOfDragons,
-                schedules: vec![
-                    EventSchedule {
-                        name: "Jade Maw",
-                        offset: NaiveTime::from_hms(0, 5, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(8),
-                    },
-                    EventSchedule {
-                        name: "Preparations",
-                        offset: NaiveTime::from_hms(0, 13, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(32),
-                    },
-                    EventSchedule {
-                        name: "Jade Maw",
-                        offset: NaiveTime::from_hms(0, 45, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(8),
-                    },
-                    EventSchedule {
-                        name: "Preparations",
-                        offset: NaiveTime::from_hms(0, 53, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::minutes(8),
-                    },
-                    EventSchedule {
-                        name: "The Battle for the Jade Sea",
-                        offset: NaiveTime::from_hms(1, 0, 0),
-                        frequency: Duration::hours(2),
-                        length: Duration::hours(1),
-                    },
-                ],
+                schedules: Cow::Borrowed(&DRAGONS_END),
             },
         }
     }
 }
+
+static DAY_AND_NIGHT: [EventSchedule; 4] = [
+    EventSchedule {
+        name: "Dawn",
+        offset: NaiveTime::from_hms_opt(0, 25, 0).unwrap(),
+        length: Duration::minutes(5),
+        frequency: Duration::hours(2),
+    },
+    EventSchedule {
+        name: "Day",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        length: Duration::minutes(70),
+        frequency: Duration::hours(2),
+    },
+    EventSchedule {
+        name: "Dusk",
+        offset: NaiveTime::from_hms_opt(1, 40, 0).unwrap(),
+        length: Duration::minutes(5),
+        frequency: Duration::hours(2),
+    },
+    EventSchedule {
+        name: "Night",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        length: Duration::minutes(40),
+        frequency: Duration::hours(2),
+    },
+];
+
+static WORLD_BOSSES: [EventSchedule; 10] = [
+    EventSchedule {
+        name: "Admiral Taidha Covington",
+        offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        frequency: Duration::hours(3),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Svanir Shaman Chief",
+        offset: NaiveTime::from_hms_opt(0, 15, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Megadestroyer",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(3),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Fire Elemental",
+        offset: NaiveTime::from_hms_opt(0, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "The Shatterer",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(3),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Great Jungle Wurm",
+        offset: NaiveTime::from_hms_opt(1, 15, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Modniir Ulgoth",
+        offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+        frequency: Duration::hours(3),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Shadow Behemoth",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Golem Mark II",
+        offset: NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+        frequency: Duration::hours(3),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Claw of Jormag",
+        offset: NaiveTime::from_hms_opt(2, 30, 0).unwrap(),
+        frequency: Duration::hours(3),
+        length: Duration::minutes(15),
+    },
+];
+
+static HARD_WORLD_BOSSES: [EventSchedule; 18] = [
+    EventSchedule {
+        name: "Tequatl the Sunless",
+        offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Triple Trouble",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Karka Queen",
+        offset: NaiveTime::from_hms_opt(2, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Tequatl the Sunless",
+        offset: NaiveTime::from_hms_opt(3, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Triple Trouble",
+        offset: NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Karka Queen",
+        offset: NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Tequatl the Sunless",
+        offset: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Triple Trouble",
+        offset: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Karka Queen",
+        offset: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Tequatl the Sunless",
+        offset: NaiveTime::from_hms_opt(11, 30, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Triple Trouble",
+        offset: NaiveTime::from_hms_opt(12, 30, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Karka Queen",
+        offset: NaiveTime::from_hms_opt(15, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Tequatl the Sunless",
+        offset: NaiveTime::from_hms_opt(16, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Triple Trouble",
+        offset: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Karka Queen",
+        offset: NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Tequatl the Sunless",
+        offset: NaiveTime::from_hms_opt(19, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Triple Trouble",
+        offset: NaiveTime::from_hms_opt(20, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Karka Queen",
+        offset: NaiveTime::from_hms_opt(23, 0, 0).unwrap(),
+        frequency: Duration::hours(24),
+        length: Duration::minutes(30),
+    },
+];
+
+static LEY_LINE_ANOMALY: [EventSchedule; 3] = [
+    EventSchedule {
+        name: "Timberline Falls",
+        offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+        frequency: Duration::hours(6),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Iron Marches",
+        offset: NaiveTime::from_hms_opt(2, 20, 0).unwrap(),
+        frequency: Duration::hours(6),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Gendarran Fields",
+        offset: NaiveTime::from_hms_opt(4, 20, 0).unwrap(),
+        frequency: Duration::hours(6),
+        length: Duration::minutes(20),
+    },
+];
+
+static TWISTED_MARIONETTE: [EventSchedule; 1] = [EventSchedule {
+    name: "Twisted Marionette (Public)",
+    offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::minutes(20),
+}];
+
+static PVP_TOURNAMENTS: [EventSchedule; 4] = [
+    EventSchedule {
+        name: "Balthazar's Brawl",
+        offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        frequency: Duration::hours(12),
+        length: Duration::hours(1),
+    },
+    EventSchedule {
+        name: "Grenth's Game",
+        offset: NaiveTime::from_hms_opt(3, 0, 0).unwrap(),
+        frequency: Duration::hours(12),
+        length: Duration::hours(1),
+    },
+    EventSchedule {
+        name: "Melandru's Matchup",
+        offset: NaiveTime::from_hms_opt(6, 0, 0).unwrap(),
+        frequency: Duration::hours(12),
+        length: Duration::hours(1),
+    },
+    EventSchedule {
+        name: "Lyssa's Legions",
+        offset: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+        frequency: Duration::hours(12),
+        length: Duration::hours(1),
+    },
+];
+
+static DRY_TOP: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "Crash Site",
+        offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        frequency: Duration::hours(1),
+        length: Duration::minutes(40),
+    },
+    EventSchedule {
+        name: "Sandstorm",
+        offset: NaiveTime::from_hms_opt(0, 40, 0).unwrap(),
+        frequency: Duration::hours(1),
+        length: Duration::minutes(20),
+    },
+];
+
+static VERDANT_BRINK: [EventSchedule; 3] = [
+    EventSchedule {
+        name: "Night: Night and the Enemy",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(25),
+    },
+    EventSchedule {
+        name: "Night Bosses",
+        offset: NaiveTime::from_hms_opt(0, 10, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Day: Securing Verdant Brink",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(75),
+    },
+];
+
+static AURIC_BASIN: [EventSchedule; 4] = [
+    EventSchedule {
+        name: "Challenges",
+        offset: NaiveTime::from_hms_opt(0, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Octovine",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Reset",
+        offset: NaiveTime::from_hms_opt(1, 20, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(10),
+    },
+    EventSchedule {
+        name: "Pylons",
+        offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(75),
+    },
+];
+
+static TANGLED_DEPTHS: [EventSchedule; 3] = [
+    EventSchedule {
+        name: "Prep",
+        offset: NaiveTime::from_hms_opt(0, 25, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(5),
+    },
+    EventSchedule {
+        name: "Chak Gerent",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Help the Outposts",
+        offset: NaiveTime::from_hms_opt(0, 50, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(95),
+    },
+];
+
+static DRAGONS_STAND: [EventSchedule; 1] = [EventSchedule {
+    name: "Start advancing on the Blighting Towers",
+    offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::hours(2),
+}];
+
+static LAKE_DORIC: [EventSchedule; 3] = [
+    EventSchedule {
+        name: "Noran's Homestead",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Saidra's Haven",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(45),
+    },
+    EventSchedule {
+        name: "New Loamhurst",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(45),
+    },
+];
+
+static CRYSTAL_OASIS: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "Rounds 1 to 3",
+        offset: NaiveTime::from_hms_opt(0, 5, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(10),
+    },
+    EventSchedule {
+        name: "Pinata/Reset",
+        offset: NaiveTime::from_hms_opt(0, 20, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(10),
+    },
+];
+
+static DESERT_HIGHLANDS: [EventSchedule; 1] = [EventSchedule {
+    name: "Buried Treasure",
+    offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::minutes(20),
+}];
+
+static ELON_RIVERLANDS: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "The Path to Ascension: Augury Rock",
+        offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(25),
+    },
+    EventSchedule {
+        name: "Doppelganger",
+        offset: NaiveTime::from_hms_opt(1, 50, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+];
+
+static THE_DESOLATION: [EventSchedule; 3] = [
+    EventSchedule {
+        name: "Junudu Rising",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Maws of Torment",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Junudu Rising",
+        offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+];
+
+static DOMAIN_OF_VABBI: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "Forged with Fire",
+        offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        frequency: Duration::hours(1),
+        length: Duration::minutes(30),
+    },
+    EventSchedule {
+        name: "Serpents' Ire",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(30),
+    },
+];
+
+static DOMAIN_OF_ISTAN: [EventSchedule; 1] = [EventSchedule {
+    name: "Palawadan",
+    offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::minutes(30),
+}];
+
+static JAHAI_BLUFFS: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "Escorts",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Death-Branded Shatterer",
+        offset: NaiveTime::from_hms_opt(1, 15, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+];
+
+static THUNDERHEAD_PEAKS: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "The Oil Floes",
+        offset: NaiveTime::from_hms_opt(0, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Thunderhead Keep",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+];
+
+static GROTHMAR_VALLEY: [EventSchedule; 4] = [
+    EventSchedule {
+        name: "Effigy",
+        offset: NaiveTime::from_hms_opt(0, 10, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Doomlore Shrine",
+        offset: NaiveTime::from_hms_opt(0, 38, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(22),
+    },
+    EventSchedule {
+        name: "Ooze Pits",
+        offset: NaiveTime::from_hms_opt(1, 5, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+    EventSchedule {
+        name: "Metal Concert",
+        offset: NaiveTime::from_hms_opt(1, 40, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+];
+
+static BJORA_MARCHES: [EventSchedule; 4] = [
+    EventSchedule {
+        name: "Shards and Construct",
+        offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(5),
+    },
+    EventSchedule {
+        name: "Icebrood Champions",
+        offset: NaiveTime::from_hms_opt(0, 5, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+    EventSchedule {
+        name: "Drakkar and Spirits of the Wild",
+        offset: NaiveTime::from_hms_opt(1, 5, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(35),
+    },
+    EventSchedule {
+        name: "Raven Shrines",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(15),
+    },
+];
+
+static DRAGONSTORM: [EventSchedule; 1] = [EventSchedule {
+    name: "Dragonstorm (Public)",
+    offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::minutes(20),
+}];
+
+static CANTHA: [EventSchedule; 4] = [
+    EventSchedule {
+        name: "Dawn",
+        offset: NaiveTime::from_hms_opt(0, 25, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(5),
+    },
+    EventSchedule {
+        name: "Day",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(70),
+    },
+    EventSchedule {
+        name: "Dusk",
+        offset: NaiveTime::from_hms_opt(1, 40, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(5),
+    },
+    EventSchedule {
+        name: "Night",
+        offset: NaiveTime::from_hms_opt(1, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(40),
+    },
+];
+
+static SEITUNG_PROVINCE: [EventSchedule; 1] = [EventSchedule {
+    name: "Aetherblade Assault",
+    offset: NaiveTime::from_hms_opt(1, 30, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::minutes(30),
+}];
+
+static NEW_KAINENG_CITY: [EventSchedule; 1] = [EventSchedule {
+    name: "Kaineng Blackout",
+    offset: NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+    frequency: Duration::hours(2),
+    length: Duration::minutes(40),
+}];
+
+static THE_ECHOVALD_WILDS: [EventSchedule; 2] = [
+    EventSchedule {
+        name: "Gang War",
+        offset: NaiveTime::from_hms_opt(0, 30, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(35),
+    },
+    EventSchedule {
+        name: "Aspenwood",
+        offset: NaiveTime::from_hms_opt(1, 40, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(20),
+    },
+];
+
+static DRAGONS_END: [EventSchedule; 5] = [
+    EventSchedule {
+        name: "Jade Maw",
+        offset: NaiveTime::from_hms_opt(0, 5, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(8),
+    },
+    EventSchedule {
+        name: "Preparations",
+        offset: NaiveTime::from_hms_opt(0, 13, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(32),
+    },
+    EventSchedule {
+        name: "Jade Maw",
+        offset: NaiveTime::from_hms_opt(0, 45, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(8),
+    },
+    EventSchedule {
+        name: "Preparations",
+        offset: NaiveTime::from_hms_opt(0, 53, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::minutes(7),
+    },
+    EventSchedule {
+        name: "The Battle for the Jade Sea",
+        offset: NaiveTime::from_hms_opt(1, 0, 0).unwrap(),
+        frequency: Duration::hours(2),
+        length: Duration::hours(1),
+    },
+];
+
+impl FromStr for MapMetaKind {
+    type Err = ParseMapMetaKindError;
+
+    /// Find a map meta by its name or variant name, ignoring case, spaces and punctuation
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = loose_name(s);
+        MapMetaKind::all_keys()
+            .iter()
+            .copied()
+            .find(|kind| {
+                loose_name(kind.info().name) == name || loose_name(&format!("{:?}", kind)) == name
+            })
+            .ok_or(ParseMapMetaKindError)
+    }
+}
+
+/// The error given when parsing an unknown [MapMetaKind]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseMapMetaKindError;
+
+impl fmt::Display for ParseMapMetaKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("unknown map meta")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseMapMetaKindError {}
+
+/// Strip everything but letters and numbers from a name so names compare loosely
+pub(crate) fn loose_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
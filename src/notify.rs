@@ -0,0 +1,475 @@
+//! Delivers alerts about upcoming events
+//!
+//! Only available with the `notify` feature enabled. A [Notifier] sends a single [Alert], and a
+//! [Dispatcher] sends an alert for each [Reminder] from a [ReminderPlanner] once it's due. Wrap a
+//! notifier in [Retry] or [RateLimit] to retry failed alerts or to space them out.
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::TcpStream,
+    sync::Mutex,
+    thread,
+    time::{Duration as StdDuration, Instant},
+};
+
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+
+use crate::{
+    clock::Clock,
+    humanize::Humanizer,
+    reminder::{self, Reminder, ReminderPlanner},
+};
+
+/// A message to deliver
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Alert {
+    pub title: String,
+    pub message: String,
+}
+
+impl Alert {
+    /// Create an alert for a reminder from a planner started on `date`
+    pub fn from_reminder(reminder: &Reminder, date: NaiveDate) -> Self {
+        let start = reminder.event.start_datetime(date);
+        Alert {
+            title: reminder.event.schedule.name.to_string(),
+            message: format!(
+                "{} starts in {} at {}",
+                reminder.event.schedule.name,
+                Humanizer::default().duration(reminder.lead_time),
+                start.format("%H:%M UTC")
+            ),
+        }
+    }
+}
+
+/// The reason an alert couldn't be delivered
+#[derive(Debug)]
+pub enum NotifyError {
+    /// The server responded with an error status
+    Status(u16),
+
+    /// The server asked for fewer alerts, optionally saying how long to wait
+    RateLimited(Option<StdDuration>),
+
+    /// The mail server rejected a command
+    Smtp(String),
+
+    /// An email address contains a line break, which could inject commands or headers
+    InvalidAddress(String),
+
+    /// The alert couldn't be sent
+    Io(io::Error),
+}
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyError::Status(status) => write!(f, "server responded with status {}", status),
+            NotifyError::RateLimited(_) => f.write_str("rate limited by server"),
+            NotifyError::Smtp(reply) => write!(f, "mail server replied: {}", reply),
+            NotifyError::InvalidAddress(address) => {
+                write!(f, "invalid email address: {:?}", address)
+            }
+            NotifyError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl Error for NotifyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NotifyError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for NotifyError {
+    fn from(e: io::Error) -> Self {
+        NotifyError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for NotifyError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(429, response) => NotifyError::RateLimited(
+                response
+                    .header("Retry-After")
+                    .and_then(|seconds| seconds.parse::<u64>().ok())
+                    .map(StdDuration::from_secs),
+            ),
+            ureq::Error::Status(status, _) => NotifyError::Status(status),
+            ureq::Error::Transport(e) => NotifyError::Io(io::Error::other(e.to_string())),
+        }
+    }
+}
+
+/// Something that can deliver an [Alert]
+pub trait Notifier {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError>;
+}
+
+/// The JSON body a webhook expects
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// `{"content": ...}`
+    Discord,
+
+    /// `{"text": ...}`
+    Slack,
+}
+
+/// Posts alerts as JSON to a Discord or Slack compatible webhook
+pub struct WebhookNotifier {
+    url: String,
+    format: WebhookFormat,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: &str, format: WebhookFormat) -> Self {
+        WebhookNotifier {
+            url: url.to_string(),
+            format,
+        }
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError> {
+        let body = match self.format {
+            WebhookFormat::Discord => serde_json::json!({
+                "content": format!("**{}**\n{}", alert.title, alert.message)
+            }),
+            WebhookFormat::Slack => serde_json::json!({
+                "text": format!("*{}*\n{}", alert.title, alert.message)
+            }),
+        };
+        ureq::post(&self.url)
+            .set("Content-Type", "application/json")
+            .send_string(&body.to_string())?;
+        Ok(())
+    }
+}
+
+/// Sends alerts to a push notification service
+pub enum PushNotifier {
+    /// An ntfy topic URL, e.g. `https://ntfy.sh/my-topic`
+    Ntfy { url: String },
+
+    /// A Gotify server URL and application token
+    Gotify { url: String, token: String },
+}
+
+impl Notifier for PushNotifier {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError> {
+        match self {
+            PushNotifier::Ntfy { url } => {
+                ureq::post(url)
+                    .set("Title", &alert.title)
+                    .send_string(&alert.message)?;
+            }
+            PushNotifier::Gotify { url, token } => {
+                let body = serde_json::json!({
+                    "title": alert.title,
+                    "message": alert.message,
+                });
+                ureq::post(&format!("{}/message", url.trim_end_matches('/')))
+                    .set("X-Gotify-Key", token)
+                    .set("Content-Type", "application/json")
+                    .send_string(&body.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Sends alerts as email through an SMTP server
+///
+/// The connection is plain SMTP without TLS or authentication, so this is meant for a local mail
+/// relay.
+pub struct EmailNotifier {
+    /// The `host:port` of the SMTP server
+    server: String,
+    from: String,
+    to: Vec<String>,
+}
+
+impl EmailNotifier {
+    pub fn new(server: &str, from: &str, to: &[&str]) -> Self {
+        EmailNotifier {
+            server: server.to_string(),
+            from: from.to_string(),
+            to: to.iter().map(|to| to.to_string()).collect(),
+        }
+    }
+}
+
+impl Notifier for EmailNotifier {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError> {
+        if let Some(address) = core::iter::once(&self.from)
+            .chain(&self.to)
+            .find(|address| address.contains(['\r', '\n']))
+        {
+            return Err(NotifyError::InvalidAddress(address.clone()));
+        }
+
+        let stream = TcpStream::connect(&self.server)?;
+        stream.set_read_timeout(Some(StdDuration::from_secs(30)))?;
+        let mut smtp = Smtp {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+        };
+
+        smtp.reply(220)?;
+        smtp.command("EHLO gw2timers", 250)?;
+        smtp.command(&format!("MAIL FROM:<{}>", self.from), 250)?;
+        for to in &self.to {
+            smtp.command(&format!("RCPT TO:<{}>", to), 250)?;
+        }
+        smtp.command("DATA", 354)?;
+
+        let mut data = format!(
+            "From: <{}>\r\nTo: {}\r\nSubject: {}\r\n\r\n",
+            self.from,
+            self.to
+                .iter()
+                .map(|to| format!("<{}>", to))
+                .collect::<Vec<String>>()
+                .join(", "),
+            // Event names end up in the subject, so they mustn't be able to start a new header
+            alert.title.replace(['\r', '\n'], " ")
+        );
+        for line in alert.message.lines() {
+            let line = line.replace('\r', "");
+            // Lines starting with a dot would otherwise end the message early
+            if line.starts_with('.') {
+                data.push('.');
+            }
+            data.push_str(&line);
+            data.push_str("\r\n");
+        }
+        data.push('.');
+        smtp.command(&data, 250)?;
+        smtp.command("QUIT", 221)?;
+        Ok(())
+    }
+}
+
+struct Smtp {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Smtp {
+    fn command(&mut self, command: &str, expected: u16) -> Result<(), NotifyError> {
+        self.writer.write_all(command.as_bytes())?;
+        self.writer.write_all(b"\r\n")?;
+        self.reply(expected)
+    }
+
+    /// Read a reply, which may be spread over several `250-` lines
+    fn reply(&mut self, expected: u16) -> Result<(), NotifyError> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(NotifyError::Smtp(String::from("connection closed")));
+            }
+            let line = line.trim_end();
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+            return match line.get(..3).and_then(|code| code.parse::<u16>().ok()) {
+                Some(code) if code == expected => Ok(()),
+                _ => Err(NotifyError::Smtp(line.to_string())),
+            };
+        }
+    }
+}
+
+/// Writes alerts as lines to stdout or any other writer
+pub struct LogNotifier<W> {
+    writer: Mutex<W>,
+}
+
+impl LogNotifier<io::Stdout> {
+    pub fn stdout() -> Self {
+        LogNotifier::new(io::stdout())
+    }
+}
+
+impl<W: Write> LogNotifier<W> {
+    pub fn new(writer: W) -> Self {
+        LogNotifier {
+            writer: Mutex::new(writer),
+        }
+    }
+
+    /// Take back the writer
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap()
+    }
+}
+
+impl<W: Write> Notifier for LogNotifier<W> {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError> {
+        let mut writer = self.writer.lock().unwrap();
+        writeln!(writer, "{}: {}", alert.title, alert.message)?;
+        Ok(())
+    }
+}
+
+/// The longest [Retry] waits between attempts once its backoff has doubled enough times
+const MAX_BACKOFF: StdDuration = StdDuration::from_secs(60 * 60);
+
+/// Retries alerts that fail to send
+///
+/// The wait between attempts doubles each time up to an hour, unless the server says how long to
+/// wait.
+pub struct Retry<N> {
+    notifier: N,
+    attempts: u32,
+    backoff: StdDuration,
+}
+
+impl<N: Notifier> Retry<N> {
+    /// Try each alert up to `attempts` times, first waiting `backoff` between attempts
+    pub fn new(notifier: N, attempts: u32, backoff: StdDuration) -> Self {
+        Retry {
+            notifier,
+            attempts: attempts.max(1),
+            backoff,
+        }
+    }
+}
+
+impl<N: Notifier> Notifier for Retry<N> {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError> {
+        let mut backoff = self.backoff;
+        let mut attempt = 1;
+        loop {
+            match self.notifier.notify(alert) {
+                Ok(()) => return Ok(()),
+                Err(e) if attempt >= self.attempts => return Err(e),
+                Err(NotifyError::RateLimited(Some(wait))) => thread::sleep(wait),
+                Err(_) => thread::sleep(backoff),
+            }
+            backoff = double_backoff(backoff, self.backoff);
+            attempt += 1;
+        }
+    }
+}
+
+/// Double `backoff` without overflowing, capped at an hour unless the `initial` backoff was longer
+pub(crate) fn double_backoff(backoff: StdDuration, initial: StdDuration) -> StdDuration {
+    backoff.saturating_mul(2).min(MAX_BACKOFF.max(initial))
+}
+
+/// Spaces out alerts so no more than one is sent every `interval`
+pub struct RateLimit<N> {
+    notifier: N,
+    interval: StdDuration,
+    last: Mutex<Option<Instant>>,
+}
+
+impl<N: Notifier> RateLimit<N> {
+    pub fn new(notifier: N, interval: StdDuration) -> Self {
+        RateLimit {
+            notifier,
+            interval,
+            last: Mutex::new(None),
+        }
+    }
+}
+
+impl<N: Notifier> Notifier for RateLimit<N> {
+    fn notify(&self, alert: &Alert) -> Result<(), NotifyError> {
+        let mut last = self.last.lock().unwrap();
+        if let Some(last) = *last {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                thread::sleep(self.interval - elapsed);
+            }
+        }
+        *last = Some(Instant::now());
+        self.notifier.notify(alert)
+    }
+}
+
+/// Sends an [Alert] through a [Notifier] for each [Reminder] as it becomes due
+pub struct Dispatcher<N> {
+    notifier: N,
+    reminders: reminder::IntoIter,
+
+    /// The day the reminders' times are from
+    date: NaiveDate,
+
+    /// The next reminder to send
+    next: Option<Reminder>,
+}
+
+impl<N: Notifier> Dispatcher<N> {
+    /// Send the reminders from `planner` that are due from `start` onwards
+    pub fn new(notifier: N, planner: ReminderPlanner, start: DateTime<Utc>) -> Self {
+        let mut reminders = planner.into_iter().time(start.time());
+        Dispatcher {
+            notifier,
+            next: reminders.next(),
+            reminders,
+            date: start.date_naive(),
+        }
+    }
+
+    /// The time the next reminder is due, unless it's past the range of a [DateTime]
+    pub fn next_due(&self) -> Option<DateTime<Utc>> {
+        self.next.as_ref().and_then(|reminder| {
+            self.date
+                .and_time(NaiveTime::MIN)
+                .and_utc()
+                .checked_add_signed(reminder.time)
+        })
+    }
+
+    /// Send every reminder due at or before `now`, giving the result of each
+    pub fn poll(&mut self, now: DateTime<Utc>) -> Vec<Result<Alert, NotifyError>> {
+        let mut results = Vec::new();
+        while self.next_due().is_some_and(|due| due <= now) {
+            if let Some(reminder) = self.next.take() {
+                let alert = Alert::from_reminder(&reminder, self.date);
+                results.push(self.notifier.notify(&alert).map(|_| alert));
+            }
+            self.next = self.reminders.next();
+        }
+        results
+    }
+
+    /// Keep sending reminders as they become due, calling `on_result` with the result of each
+    ///
+    /// This sleeps until each reminder is due, so `clock` must move on by itself, like a
+    /// [SystemClock](crate::clock::SystemClock). With a [FixedClock](crate::clock::FixedClock) it
+    /// never returns. Use [Dispatcher::run_with] to wait some other way.
+    pub fn run(&mut self, clock: &impl Clock, on_result: impl FnMut(Result<Alert, NotifyError>)) {
+        self.run_with(clock, thread::sleep, on_result)
+    }
+
+    /// Keep sending reminders as they become due, calling `wait` with how long until the next one
+    ///
+    /// `wait` should return once `clock` has moved on by that long, like by advancing a
+    /// [ManualClock](crate::clock::ManualClock).
+    pub fn run_with(
+        &mut self,
+        clock: &impl Clock,
+        mut wait: impl FnMut(StdDuration),
+        mut on_result: impl FnMut(Result<Alert, NotifyError>),
+    ) {
+        while let Some(due) = self.next_due() {
+            if let Ok(until) = (due - clock.now()).to_std() {
+                wait(until);
+            }
+            self.poll(clock.now()).into_iter().for_each(&mut on_result);
+        }
+    }
+}
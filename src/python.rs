@@ -0,0 +1,248 @@
+//! Python bindings
+//!
+//! Only available with the `python` feature enabled. The module is built with
+//! [maturin](https://www.maturin.rs), e.g. `maturin develop`. Times are given as timezone aware
+//! `datetime`s, or the time right now when left out, and returned in UTC:
+//!
+//! ```python
+//! import itertools
+//! from gw2timers import MapMetaKind
+//!
+//! for event in itertools.islice(MapMetaKind.WorldBosses.iter_from(), 5):
+//!     print(event.name, event.start)
+//! ```
+
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, Utc};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    clock::{Clock, FixedClock, SystemClock},
+    event::EventInstance,
+    meta::{self, MapMetaKind},
+    schedule::{self, EventSchedule},
+};
+
+fn datetime(at: Option<DateTime<FixedOffset>>) -> DateTime<Utc> {
+    match at {
+        Some(at) => at.with_timezone(&Utc),
+        None => SystemClock.now(),
+    }
+}
+
+#[pymethods]
+impl MapMetaKind {
+    /// Every map meta
+    #[staticmethod]
+    fn all() -> Vec<MapMetaKind> {
+        MapMetaKind::all_keys().to_vec()
+    }
+
+    /// Find a map meta by its name, ignoring case, spaces and punctuation
+    #[staticmethod]
+    fn from_name(name: &str) -> PyResult<MapMetaKind> {
+        name.parse()
+            .map_err(|e| PyValueError::new_err(format!("{}: '{}'", e, name)))
+    }
+
+    /// The display name of the map meta
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// The display name of the map meta's category
+    #[getter]
+    fn category(&self) -> &'static str {
+        self.info().category.name()
+    }
+
+    /// The schedules of every event in the map meta
+    #[getter]
+    fn schedules(&self) -> Vec<PyEventSchedule> {
+        self.info()
+            .schedules
+            .iter()
+            .cloned()
+            .map(PyEventSchedule)
+            .collect()
+    }
+
+    /// Iterate over the events that start after `now`
+    #[pyo3(name = "iter_from", signature = (now=None))]
+    fn py_iter_from(&self, now: Option<DateTime<FixedOffset>>) -> PyMetaIter {
+        let now = datetime(now);
+        PyMetaIter {
+            date: now.date_naive(),
+            iter: self.iter_from(&FixedClock::new(now)),
+        }
+    }
+
+    /// The event active at `at`, if any
+    #[pyo3(signature = (at=None))]
+    fn active(&self, at: Option<DateTime<FixedOffset>>) -> Option<PyEventInstance> {
+        let at = datetime(at);
+        self.iter_from(&FixedClock::new(at))
+            .now()
+            .map(|event| PyEventInstance::new(event, at.date_naive()))
+    }
+}
+
+/// How often an event happens
+#[pyclass(name = "EventSchedule", module = "gw2timers", frozen)]
+#[derive(Clone)]
+pub struct PyEventSchedule(EventSchedule);
+
+#[pymethods]
+impl PyEventSchedule {
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.name
+    }
+
+    /// The UTC time of day the event first starts
+    #[getter]
+    fn offset(&self) -> NaiveTime {
+        self.0.offset
+    }
+
+    /// The time between each start of the event
+    #[getter]
+    fn frequency(&self) -> Duration {
+        self.0.frequency
+    }
+
+    /// How long the event lasts
+    #[getter]
+    fn length(&self) -> Duration {
+        self.0.length
+    }
+
+    /// Iterate over the occurrences of the event that start after `now`
+    #[pyo3(signature = (now=None))]
+    fn iter_from(&self, now: Option<DateTime<FixedOffset>>) -> PyScheduleIter {
+        let now = datetime(now);
+        PyScheduleIter {
+            date: now.date_naive(),
+            iter: self.0.clone().into_iter().time(now.time()),
+        }
+    }
+
+    /// The occurrence of the event active at `at`, if any
+    #[pyo3(signature = (at=None))]
+    fn active(&self, at: Option<DateTime<FixedOffset>>) -> Option<PyEventInstance> {
+        let at = datetime(at);
+        self.0
+            .iter_from(&FixedClock::new(at))
+            .now()
+            .map(|event| PyEventInstance::new(event, at.date_naive()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EventSchedule(name='{}', offset={}, frequency={}m, length={}m)",
+            self.0.name,
+            self.0.offset.format("%H:%M"),
+            self.0.frequency.num_minutes(),
+            self.0.length.num_minutes()
+        )
+    }
+}
+
+/// A single occurrence of an event
+#[pyclass(name = "EventInstance", module = "gw2timers", frozen)]
+pub struct PyEventInstance {
+    event: EventInstance,
+
+    /// The UTC day the event's start time is counted from
+    date: NaiveDate,
+}
+
+impl PyEventInstance {
+    fn new(event: EventInstance, date: NaiveDate) -> Self {
+        PyEventInstance { event, date }
+    }
+}
+
+#[pymethods]
+impl PyEventInstance {
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.event.schedule.name
+    }
+
+    #[getter]
+    fn schedule(&self) -> PyEventSchedule {
+        PyEventSchedule(self.event.schedule.clone().into_owned())
+    }
+
+    #[getter]
+    fn start(&self) -> DateTime<Utc> {
+        self.event.start_datetime(self.date)
+    }
+
+    #[getter]
+    fn end(&self) -> DateTime<Utc> {
+        self.event.end_datetime(self.date)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "EventInstance(name='{}', start={}, end={})",
+            self.event.schedule.name,
+            self.start().to_rfc3339(),
+            self.end().to_rfc3339()
+        )
+    }
+}
+
+/// An iterator over the events in a map meta
+#[pyclass(name = "MapMetaIter", module = "gw2timers")]
+pub struct PyMetaIter {
+    date: NaiveDate,
+    iter: meta::IntoIter,
+}
+
+#[pymethods]
+impl PyMetaIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<PyEventInstance> {
+        let date = self.date;
+        self.iter
+            .next()
+            .map(|event| PyEventInstance::new(event, date))
+    }
+}
+
+/// An iterator over the occurrences of an event
+#[pyclass(name = "EventScheduleIter", module = "gw2timers")]
+pub struct PyScheduleIter {
+    date: NaiveDate,
+    iter: schedule::IntoIter,
+}
+
+#[pymethods]
+impl PyScheduleIter {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self) -> Option<PyEventInstance> {
+        let date = self.date;
+        self.iter
+            .next()
+            .map(|event| PyEventInstance::new(event, date))
+    }
+}
+
+#[pymodule]
+fn gw2timers(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<MapMetaKind>()?;
+    module.add_class::<PyEventSchedule>()?;
+    module.add_class::<PyEventInstance>()?;
+    module.add_class::<PyMetaIter>()?;
+    module.add_class::<PyScheduleIter>()?;
+    Ok(())
+}
@@ -0,0 +1,184 @@
+//! Plans reminder alarms ahead of the events in one or more [EventSchedule]s
+
+use core::ops::Add;
+
+use alloc::vec::Vec;
+
+use chrono::{Duration, NaiveTime, Timelike};
+
+use crate::{cursor::Cursor, event::EventInstance, schedule::EventSchedule};
+
+/// An alarm that should fire some amount of time before an [EventInstance] starts
+#[derive(Clone, Debug)]
+pub struct Reminder {
+    /// The time from UTC 00:00 the alarm should fire
+    pub time: Duration,
+
+    /// How long before the event starts the alarm fires
+    pub lead_time: Duration,
+
+    /// The event the alarm is for
+    pub event: EventInstance,
+}
+
+/// Computes reminder alarms for a set of [EventSchedule]s and lead times
+///
+/// Iterating a planner gives every alarm in order of the time it should fire. Alarms for the same
+/// event at the same time (e.g. duplicate schedules or lead times) are only given once.
+pub struct ReminderPlanner {
+    /// The schedules to give reminders for
+    pub schedules: Vec<EventSchedule>,
+
+    /// How long before each event to give a reminder
+    pub lead_times: Vec<Duration>,
+}
+
+impl ReminderPlanner {
+    pub fn new(schedules: impl Into<Vec<EventSchedule>>, lead_times: Vec<Duration>) -> Self {
+        ReminderPlanner {
+            schedules: schedules.into(),
+            lead_times,
+        }
+    }
+}
+
+impl IntoIterator for ReminderPlanner {
+    type Item = Reminder;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let mut lead_times = self.lead_times;
+        lead_times.sort_by(|a, b| b.cmp(a));
+        lead_times.dedup();
+
+        let alarms = self
+            .schedules
+            .into_iter()
+            .flat_map(|schedule| {
+                let cursor = schedule.into_iter();
+                lead_times.iter().map(move |lead_time| Alarm {
+                    cursor: cursor.clone(),
+                    lead_time: *lead_time,
+                    next: None,
+                })
+            })
+            .collect();
+
+        IntoIter {
+            current_time: Duration::zero(),
+            alarms,
+            started: false,
+        }
+    }
+}
+
+/// A single reminder for a single schedule
+struct Alarm {
+    cursor: Cursor,
+    lead_time: Duration,
+
+    /// The next reminder this alarm will give
+    next: Option<Reminder>,
+}
+
+impl Alarm {
+    /// Find the first reminder that fires at or after `time`
+    fn seek(&mut self, time: Duration) {
+        // Event times are in whole minutes, so round up to the next minute and look for events
+        // starting after the minute before it.
+        let minutes = time.num_seconds().add(59).div_euclid(60);
+        let start =
+            Duration::try_minutes(minutes - 1).and_then(|start| start.checked_add(&self.lead_time));
+
+        // Past the range of a Duration there are no events, so stop at the end of it
+        let furthest = Duration::milliseconds(i64::MAX);
+        self.cursor.seek(match start {
+            Some(start) => start,
+            None if time < Duration::zero() => -furthest,
+            None => furthest,
+        });
+        self.advance();
+    }
+
+    /// Move to the reminder for the event after the current one
+    fn advance(&mut self) {
+        let lead_time = self.lead_time;
+        self.next = self.cursor.next().and_then(|event| {
+            Some(Reminder {
+                time: event.start_time.checked_sub(&lead_time)?,
+                lead_time,
+                event,
+            })
+        });
+    }
+}
+
+/// An iterator that gives the next [Reminder] from a [ReminderPlanner]
+///
+/// The first call to `next()` will return the first reminder that fires at or after the requested
+/// time.
+pub struct IntoIter {
+    /// Respresents the amount of time from UTC 00:00
+    current_time: Duration,
+
+    alarms: Vec<Alarm>,
+
+    /// Whether the alarms have been positioned at `current_time` yet
+    started: bool,
+}
+
+impl IntoIter {
+    /// Skip to a certain time of day
+    pub fn time(mut self, time: NaiveTime) -> Self {
+        self.current_time = Duration::seconds(time.num_seconds_from_midnight() as i64);
+        self.started = false;
+        self
+    }
+
+    /// Skip forward an amount of time
+    pub fn fast_forward(mut self, amount: Duration) -> Self {
+        let furthest = Duration::milliseconds(i64::MAX);
+        self.current_time = match self.current_time.checked_add(&amount) {
+            Some(time) => time,
+            None if amount < Duration::zero() => -furthest,
+            None => furthest,
+        };
+        self.started = false;
+        self
+    }
+}
+
+impl Iterator for IntoIter {
+    type Item = Reminder;
+
+    fn next(&mut self) -> Option<Reminder> {
+        if !self.started {
+            let time = self.current_time;
+            self.alarms.iter_mut().for_each(|alarm| alarm.seek(time));
+            self.started = true;
+        }
+
+        let reminder = self
+            .alarms
+            .iter()
+            .filter_map(|alarm| alarm.next.as_ref())
+            .min_by_key(|reminder| (reminder.time, reminder.event.start_time))?
+            .clone();
+
+        // Every alarm giving this same reminder moves on, so it is only given once
+        self.alarms
+            .iter_mut()
+            .filter(|alarm| match &alarm.next {
+                Some(next) => {
+                    next.time == reminder.time
+                        && next.event.start_time == reminder.event.start_time
+                        && next.event.schedule.name == reminder.event.schedule.name
+                }
+                None => false,
+            })
+            .for_each(Alarm::advance);
+
+        self.current_time = reminder.time;
+        Some(reminder)
+    }
+}
@@ -0,0 +1,256 @@
+//! Plans a route through the events a commander wants to attend
+//!
+//! A [RoutePlanner] is given a list of [Target]s, each an event of a map meta with how much it is
+//! worth and how long it takes to get there. [RoutePlanner::plan] picks the occurrences within a
+//! window of time that are worth the most in total while never needing to be in two places at
+//! once, and lists every occurrence it left out along with why, as well as any target that isn't
+//! an event of its map meta.
+
+use alloc::{borrow::Cow, string::String, vec, vec::Vec};
+
+use chrono::Duration;
+
+use crate::{
+    cursor::Cursor,
+    cycle::CycleTable,
+    event::EventInstance,
+    meta::{loose_name, MapMetaKind},
+    schedule::EventSchedule,
+};
+
+/// An event to attend
+#[derive(Clone, Debug)]
+pub struct Target {
+    pub meta: MapMetaKind,
+
+    /// The name of the event, ignoring case, spaces and punctuation
+    pub name: String,
+
+    /// How much attending the event is worth, 1 by default. Events worth nothing are never picked
+    pub value: u32,
+
+    /// How long before the event starts to set off, e.g. to travel there and prepare
+    pub travel: Duration,
+}
+
+impl Target {
+    pub fn new(meta: MapMetaKind, name: &str) -> Self {
+        Target {
+            meta,
+            name: String::from(name),
+            value: 1,
+            travel: Duration::zero(),
+        }
+    }
+
+    /// Set how much attending the event is worth
+    pub fn value(mut self, value: u32) -> Self {
+        self.value = value;
+        self
+    }
+
+    /// Set how long before the event starts to set off
+    pub fn travel(mut self, travel: Duration) -> Self {
+        self.travel = travel;
+        self
+    }
+
+    /// Every occurrence of the target's event starting from `start` until `end`, from UTC 00:00
+    pub(crate) fn stops(&self, index: usize, start: Duration, end: Duration) -> Vec<Stop> {
+        let mut events = Cursor::new(Cow::Owned(CycleTable::new(self.schedules())));
+        // Events start on whole minutes, so this also finds those starting right at `start`
+        events.seek(start - Duration::minutes(1));
+        events
+            .take_while(|event| event.start_time < end)
+            .filter(|event| event.start_time >= start)
+            .map(|event| Stop {
+                target: index,
+                depart: event.start_time - self.travel,
+                event,
+                value: self.value,
+            })
+            .collect()
+    }
+
+    /// Whether the target's name is one of the map meta's events
+    pub fn is_known(&self) -> bool {
+        let name = loose_name(&self.name);
+        self.meta
+            .info()
+            .schedules
+            .iter()
+            .any(|schedule| loose_name(schedule.name) == name)
+    }
+
+    /// The schedules of the map meta's events with the target's name
+    fn schedules(&self) -> Vec<EventSchedule> {
+        let name = loose_name(&self.name);
+        self.meta
+            .info()
+            .schedules
+            .iter()
+            .filter(|schedule| loose_name(schedule.name) == name)
+            .cloned()
+            .collect()
+    }
+}
+
+/// An occurrence of a [Target]'s event
+#[derive(Clone, Debug)]
+pub struct Stop {
+    /// The index of the target in [RoutePlanner::targets]
+    pub target: usize,
+
+    pub event: EventInstance,
+
+    /// The time from UTC 00:00 to set off for the event
+    pub depart: Duration,
+
+    pub value: u32,
+}
+
+impl Stop {
+    /// The time from UTC 00:00 the event ends, and the commander is free again
+    pub fn end_time(&self) -> Duration {
+        self.event.end_time()
+    }
+
+    /// Whether the commander would need to be at both stops at once
+    pub(crate) fn conflicts(&self, other: &Stop) -> bool {
+        self.depart < other.end_time() && other.depart < self.end_time()
+    }
+}
+
+/// Why a [Stop] was left out of a [Route]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Skip {
+    /// The commander would have to set off before the window starts, or the event ends after it
+    OutsideWindow,
+
+    /// The stops of the route at these indexes were worth more together
+    Conflicts(Vec<usize>),
+}
+
+/// A [Stop] that was left out of a [Route], and why
+#[derive(Clone, Debug)]
+pub struct Skipped {
+    pub stop: Stop,
+    pub reason: Skip,
+}
+
+/// The stops a [RoutePlanner] picked, and the ones it left out
+#[derive(Clone, Debug)]
+pub struct Route {
+    /// The stops to attend, in order
+    pub stops: Vec<Stop>,
+
+    /// Every other occurrence of the targets that starts within the window, in order
+    pub skipped: Vec<Skipped>,
+
+    /// The indexes of the targets whose name isn't an event of their map meta, e.g. from a typo
+    pub unknown: Vec<usize>,
+}
+
+impl Route {
+    /// The total value of every stop in the route
+    pub fn value(&self) -> u64 {
+        self.stops.iter().map(|stop| stop.value as u64).sum()
+    }
+}
+
+/// Picks which occurrences of a set of [Target]s to attend
+pub struct RoutePlanner {
+    pub targets: Vec<Target>,
+}
+
+impl RoutePlanner {
+    pub fn new(targets: Vec<Target>) -> Self {
+        RoutePlanner { targets }
+    }
+
+    /// Plan the route from `start` until `end`, from UTC 00:00
+    ///
+    /// The route is worth the most possible, with every stop's travel and event fitting within
+    /// the window without overlapping another. When routes are worth the same, the one that ends
+    /// earliest is picked.
+    pub fn plan(&self, start: Duration, end: Duration) -> Route {
+        let mut candidates = Vec::new();
+        let mut skipped = Vec::new();
+        let mut unknown = Vec::new();
+        for (index, target) in self.targets.iter().enumerate() {
+            if !target.is_known() {
+                unknown.push(index);
+                continue;
+            }
+            for stop in target.stops(index, start, end) {
+                if stop.depart < start || stop.end_time() > end {
+                    skipped.push(Skipped {
+                        stop,
+                        reason: Skip::OutsideWindow,
+                    });
+                } else {
+                    candidates.push(stop);
+                }
+            }
+        }
+
+        // Weighted interval scheduling over the stops in order of when they end
+        candidates.sort_by_key(|stop| (stop.end_time(), stop.depart, stop.target));
+        let ends = candidates
+            .iter()
+            .map(|stop| stop.end_time())
+            .collect::<Vec<Duration>>();
+        // best[i] is the most the first i candidates are worth
+        let mut best = Vec::with_capacity(candidates.len() + 1);
+        best.push(0u64);
+        // The number of candidates that end before each one sets off
+        let mut previous = Vec::with_capacity(candidates.len());
+        for (i, stop) in candidates.iter().enumerate() {
+            let before = ends[..i].partition_point(|end| *end <= stop.depart);
+            previous.push(before);
+            best.push(best[i].max(best[before] + stop.value as u64));
+        }
+
+        let mut picked = Vec::new();
+        let mut i = candidates.len();
+        while i > 0 {
+            let stop = &candidates[i - 1];
+            if best[previous[i - 1]] + stop.value as u64 > best[i - 1] {
+                picked.push(i - 1);
+                i = previous[i - 1];
+            } else {
+                i -= 1;
+            }
+        }
+        picked.reverse();
+
+        let mut is_picked = vec![false; candidates.len()];
+        picked.iter().for_each(|&i| is_picked[i] = true);
+        let stops = picked
+            .iter()
+            .map(|&i| candidates[i].clone())
+            .collect::<Vec<Stop>>();
+        for (stop, picked) in candidates.into_iter().zip(is_picked) {
+            if picked {
+                continue;
+            }
+            let conflicts = stops
+                .iter()
+                .enumerate()
+                .filter(|(_, chosen)| chosen.conflicts(&stop))
+                .map(|(index, _)| index)
+                .collect();
+            skipped.push(Skipped {
+                stop,
+                reason: Skip::Conflicts(conflicts),
+            });
+        }
+        skipped.sort_by_key(|skipped| (skipped.stop.event.start_time, skipped.stop.target));
+
+        Route {
+            stops,
+            skipped,
+            unknown,
+        }
+    }
+}
@@ -1,10 +1,16 @@
 //! Represents the schedule of a single event in a maps' [meta]
 
-use std::{fmt::Debug, ops::Add};
+use core::fmt::{self, Debug};
+
+use alloc::{borrow::Cow, vec};
 
 use chrono::{Duration, NaiveTime, Timelike};
 
-use crate::event::EventInstance;
+use crate::{clock::Clock, cursor::Cursor, cycle::CycleTable, error::Error, event::EventInstance};
+
+/// The longest an event can last in minutes, so finding the events still active at a time never
+/// looks further back than four weeks
+pub(crate) const MAX_LENGTH: i64 = 4 * 7 * 24 * 60;
 
 /// The schedule of a map meta event
 #[derive(Clone)]
@@ -23,9 +29,71 @@ pub struct EventSchedule {
 }
 
 impl EventSchedule {
-    pub fn iter(&self) -> Iter {
-        Iter::new(self, Duration::zero())
+    /// Create a schedule, checking it can be iterated
+    pub fn new(
+        name: &'static str,
+        offset: NaiveTime,
+        frequency: Duration,
+        length: Duration,
+    ) -> Result<Self, Error> {
+        let schedule = EventSchedule {
+            name,
+            offset,
+            frequency,
+            length,
+        };
+        schedule.check()?;
+        Ok(schedule)
+    }
+
+    /// Check the frequency and length are positive whole numbers of minutes, the length is at most
+    /// four weeks, and the offset is on a whole minute
+    pub fn check(&self) -> Result<(), Error> {
+        if !whole_minutes(self.frequency) {
+            return Err(Error::InvalidFrequency {
+                name: self.name,
+                frequency: self.frequency,
+            });
+        }
+        if !whole_minutes(self.length) {
+            return Err(Error::InvalidLength {
+                name: self.name,
+                length: self.length,
+            });
+        }
+        if self.length.num_minutes() > MAX_LENGTH {
+            return Err(Error::LengthTooLong {
+                name: self.name,
+                length: self.length,
+            });
+        }
+        if self.offset.second() != 0 || self.offset.nanosecond() != 0 {
+            return Err(Error::InvalidOffset {
+                name: self.name,
+                offset: self.offset,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn iter(&self) -> Cursor {
+        self.clone().into_iter()
+    }
+
+    #[deprecated(note = "iterating doesn't change the schedule, use `iter` or `Cursor::by_ref`")]
+    pub fn iter_mut(&mut self) -> Cursor {
+        self.iter()
     }
+
+    /// Iterate this schedule starting at the current time of `clock`
+    pub fn iter_from(&self, clock: &impl Clock) -> Cursor {
+        self.iter().time(clock.now().time())
+    }
+}
+
+/// Whether `duration` is a positive whole number of minutes
+pub(crate) fn whole_minutes(duration: Duration) -> bool {
+    duration >= Duration::minutes(1) && duration == Duration::minutes(duration.num_minutes())
 }
 
 impl IntoIterator for EventSchedule {
@@ -33,12 +101,12 @@ impl IntoIterator for EventSchedule {
     type IntoIter = IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter::new(self, Duration::zero())
+        Cursor::new(Cow::Owned(CycleTable::new(vec![self])))
     }
 }
 
 impl Debug for EventSchedule {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
             "{}: offset: {}, freq: {}m, len: {}m",
@@ -50,227 +118,5 @@ impl Debug for EventSchedule {
     }
 }
 
-/// An iterator that gives the next occuring [EventInstance] in an [EventSchedule]
-///
-/// The first call to `next()` will return the currently active event (i.e. the requested time is
-/// between the [EventInstance]'s start and end time), if applicable, or the next event if there
-/// isn't one currently active
-pub struct IntoIter {
-    event_schedule: EventSchedule,
-    offset: Duration,
-}
-
-impl IntoIter {
-    // Creates a new iterator starting from the previous occurance of the event
-    fn new(event_schedule: EventSchedule, current_time: Duration) -> IntoIter {
-        // Must use Durations instead of NaiveTime because the event_end_time might be over 24 hours
-        IntoIter {
-            event_schedule,
-            offset: current_time,
-        }
-    }
-
-    /// Skip to a certain time of day
-    pub fn time(mut self, time: NaiveTime) -> Self {
-        self.offset = Duration::seconds(time.num_seconds_from_midnight() as i64);
-        self
-    }
-
-    /// Skip forward an amount of time
-    pub fn fast_forward(mut self, amount: Duration) -> Self {
-        self.offset = self.offset.add(amount);
-        self
-    }
-
-    /// Get the event happening now, if any, at the current iteration of the iterator.
-    pub fn now(&self) -> Option<EventInstance> {
-        let time = self.offset.num_minutes();
-        let offset = self.event_schedule.offset.num_seconds_from_midnight() as i64 / 60;
-        let freq = self.event_schedule.frequency.num_minutes();
-        let length = self.event_schedule.length.num_minutes();
-        let i = time / freq;
-        let relative_time = time - i * freq;
-
-        if relative_time < offset || relative_time >= offset + length {
-            return None;
-        }
-
-        let offset = Duration::minutes(offset + i * freq);
-
-        Some(EventInstance {
-            schedule: self.event_schedule.clone(),
-            start_time: offset,
-        })
-    }
-}
-
-impl Iterator for IntoIter {
-    type Item = EventInstance;
-
-    fn next(&mut self) -> Option<EventInstance> {
-        let time = self.offset.num_minutes();
-        let offset = self.event_schedule.offset.num_seconds_from_midnight() as i64 / 60;
-        let freq = self.event_schedule.frequency.num_minutes();
-        let i = time / freq;
-        let relative_time = time - i * freq;
-
-        let offset = if relative_time < offset {
-            offset
-        } else {
-            offset + freq
-        };
-
-        self.offset = Duration::minutes(offset + i * freq);
-
-        Some(EventInstance {
-            schedule: self.event_schedule.clone(),
-            start_time: self.offset,
-        })
-    }
-}
-
-pub struct Iter<'a> {
-    event_schedule: &'a EventSchedule,
-    offset: Duration,
-}
-
-impl<'a> Iter<'_> {
-    // Creates a new iterator starting from the previous occurance of the event
-    fn new(event_schedule: &'a EventSchedule, current_time: Duration) -> Iter {
-        // Must use Durations instead of NaiveTime because the event_end_time might be over 24 hours
-        Iter {
-            event_schedule,
-            offset: current_time,
-        }
-    }
-
-    /// Skip to a certain time of day
-    pub fn time(mut self, time: NaiveTime) -> Self {
-        self.offset = Duration::seconds(time.num_seconds_from_midnight() as i64);
-        self
-    }
-
-    /// Skip forward an amount of time
-    pub fn fast_forward(mut self, amount: Duration) -> Self {
-        self.offset = self.offset.add(amount);
-        self
-    }
-
-    /// Get the event happening now, if any, at the current iteration of the iterator.
-    pub fn now(&self) -> Option<EventInstance> {
-        let time = self.offset.num_minutes();
-        let offset = self.event_schedule.offset.num_seconds_from_midnight() as i64 / 60;
-        let freq = self.event_schedule.frequency.num_minutes();
-        let length = self.event_schedule.length.num_minutes();
-        let i = time / freq;
-        let relative_time = time - i * freq;
-
-        if relative_time < offset || relative_time >= offset + length {
-            return None;
-        }
-
-        let offset = Duration::minutes(offset + i * freq);
-        Some(EventInstance {
-            schedule: self.event_schedule.clone(),
-            start_time: offset,
-        })
-    }
-}
-
-impl Iterator for Iter<'_> {
-    type Item = EventInstance;
-
-    fn next(&mut self) -> Option<EventInstance> {
-        let time = self.offset.num_minutes();
-        let offset = self.event_schedule.offset.num_seconds_from_midnight() as i64 / 60;
-        let freq = self.event_schedule.frequency.num_minutes();
-        let i = time / freq;
-        let relative_time = time - i * freq;
-
-        let offset = if relative_time < offset {
-            offset
-        } else {
-            offset + freq
-        };
-
-        self.offset = Duration::minutes(offset + i * freq);
-
-        Some(EventInstance {
-            schedule: self.event_schedule.clone(),
-            start_time: self.offset,
-        })
-    }
-}
-
-pub struct IterMut<'a> {
-    event_schedule: &'a mut EventSchedule,
-    offset: Duration,
-}
-
-impl<'a> IterMut<'_> {
-    // Creates a new iterator starting from the previous occurance of the event
-    fn new(event_schedule: &'a mut EventSchedule, current_time: Duration) -> IterMut {
-        // Must use Durations instead of NaiveTime because the event_end_time might be over 24 hours
-        IterMut {
-            event_schedule,
-            offset: current_time,
-        }
-    }
-
-    /// Skip to a certain time of day
-    pub fn time(mut self, time: NaiveTime) -> Self {
-        self.offset = Duration::seconds(time.num_seconds_from_midnight() as i64);
-        self
-    }
-
-    /// Skip forward an amount of time
-    pub fn fast_forward(mut self, amount: Duration) -> Self {
-        self.offset = self.offset.add(amount);
-        self
-    }
-
-    /// Get the event happening now, if any, at the current iteration of the iterator.
-    pub fn now(&self) -> Option<EventInstance> {
-        let time = self.offset.num_minutes();
-        let offset = self.event_schedule.offset.num_seconds_from_midnight() as i64 / 60;
-        let freq = self.event_schedule.frequency.num_minutes();
-        let length = self.event_schedule.length.num_minutes();
-        let i = time / freq;
-        let relative_time = time - i * freq;
-
-        if relative_time < offset || relative_time >= offset + length {
-            return None;
-        }
-
-        let offset = Duration::minutes(offset + i * freq);
-        Some(EventInstance {
-            schedule: self.event_schedule.clone(),
-            start_time: offset,
-        })
-    }
-}
-
-impl Iterator for IterMut<'_> {
-    type Item = EventInstance;
-
-    fn next(&mut self) -> Option<EventInstance> {
-        let time = self.offset.num_minutes();
-        let offset = self.event_schedule.offset.num_seconds_from_midnight() as i64 / 60;
-        let freq = self.event_schedule.frequency.num_minutes();
-        let i = time / freq;
-        let relative_time = time - i * freq;
-
-        let offset = if relative_time < offset {
-            offset
-        } else {
-            offset + freq
-        };
-
-        self.offset = Duration::minutes(offset + i * freq);
-
-        Some(EventInstance {
-            schedule: self.event_schedule.clone(),
-            start_time: self.offset,
-        })
-    }
-}
+/// An iterator over the occurrences of an [EventSchedule]
+pub type IntoIter = Cursor;
@@ -0,0 +1,249 @@
+//! An HTTP server giving map meta event times as JSON
+//!
+//! Only available with the `server` feature enabled. The endpoints are:
+//!
+//! - `GET /metas`: every map meta and its event schedules
+//! - `GET /active`: the events active right now
+//! - `GET /upcoming`: the next events to start
+//! - `GET /timetable`: every event starting on a UTC day
+//!
+//! `/active` and `/upcoming` take an `at` (RFC 3339) query parameter to look at another time than
+//! now, and `/upcoming` and `/timetable` take `meta` and `category` query parameters, each a comma
+//! separated list, to only include some map metas. `/upcoming` also takes a `limit`.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json, Router,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    category::Category,
+    clock::{Clock, FixedClock},
+    event::EventInstance,
+    meta::MapMetaKind,
+    schedule::EventSchedule,
+};
+
+type SharedClock = Arc<dyn Clock + Send + Sync>;
+
+/// Create the server's routes, using `clock` for the current time
+pub fn router(clock: impl Clock + Send + Sync + 'static) -> Router {
+    Router::new()
+        .route("/metas", get(metas))
+        .route("/active", get(active))
+        .route("/upcoming", get(upcoming))
+        .route("/timetable", get(timetable))
+        .with_state(Arc::new(clock) as SharedClock)
+}
+
+/// Serve the routes on `addr` until the server fails
+pub async fn serve(
+    addr: SocketAddr,
+    clock: impl Clock + Send + Sync + 'static,
+) -> std::io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router(clock)).await
+}
+
+/// A map meta and its event schedules
+#[derive(Serialize)]
+struct Meta {
+    id: String,
+    name: &'static str,
+    category: &'static str,
+    schedules: Vec<Schedule>,
+}
+
+#[derive(Serialize)]
+struct Schedule {
+    name: &'static str,
+    offset: String,
+    frequency_minutes: i64,
+    length_minutes: i64,
+}
+
+impl From<&EventSchedule> for Schedule {
+    fn from(schedule: &EventSchedule) -> Self {
+        Schedule {
+            name: schedule.name,
+            offset: schedule.offset.format("%H:%M").to_string(),
+            frequency_minutes: schedule.frequency.num_minutes(),
+            length_minutes: schedule.length.num_minutes(),
+        }
+    }
+}
+
+/// A single occurrence of an event
+#[derive(Serialize)]
+struct Event {
+    meta: &'static str,
+    category: &'static str,
+    name: &'static str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+}
+
+impl Event {
+    fn new(kind: MapMetaKind, event: &EventInstance, date: NaiveDate) -> Self {
+        let meta = kind.info();
+        Event {
+            meta: meta.name,
+            category: meta.category.name(),
+            name: event.schedule.name,
+            start: event.start_datetime(date),
+            end: event.end_datetime(date),
+        }
+    }
+}
+
+/// A request the server couldn't understand
+#[derive(Serialize)]
+struct BadRequest {
+    error: String,
+}
+
+impl IntoResponse for BadRequest {
+    fn into_response(self) -> Response {
+        (StatusCode::BAD_REQUEST, Json(self)).into_response()
+    }
+}
+
+#[derive(Deserialize)]
+struct Params {
+    at: Option<DateTime<Utc>>,
+    date: Option<NaiveDate>,
+    meta: Option<String>,
+    category: Option<String>,
+    limit: Option<usize>,
+}
+
+impl Params {
+    /// Get the map metas included by the `meta` and `category` parameters
+    fn metas(&self) -> Result<Vec<MapMetaKind>, BadRequest> {
+        let metas = parse_list::<MapMetaKind>(&self.meta)?;
+        let categories = parse_list::<Category>(&self.category)?;
+        Ok(MapMetaKind::all_keys()
+            .iter()
+            .copied()
+            .filter(|kind| metas.is_empty() || metas.contains(kind))
+            .filter(|kind| categories.is_empty() || categories.contains(&kind.info().category))
+            .collect())
+    }
+
+    fn at(&self, clock: &SharedClock) -> DateTime<Utc> {
+        self.at.unwrap_or_else(|| clock.now())
+    }
+}
+
+fn parse_list<T>(list: &Option<String>) -> Result<Vec<T>, BadRequest>
+where
+    T: std::str::FromStr,
+    T::Err: ToString,
+{
+    match list {
+        Some(list) => list
+            .split(',')
+            .map(|item| {
+                item.parse::<T>().map_err(|e| BadRequest {
+                    error: format!("{}: '{}'", e.to_string(), item),
+                })
+            })
+            .collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+async fn metas() -> Json<Vec<Meta>> {
+    Json(
+        MapMetaKind::all_keys()
+            .iter()
+            .map(|kind| {
+                let meta = kind.info();
+                Meta {
+                    id: format!("{:?}", kind),
+                    name: meta.name,
+                    category: meta.category.name(),
+                    schedules: meta.schedules.iter().map(Schedule::from).collect(),
+                }
+            })
+            .collect(),
+    )
+}
+
+async fn active(
+    State(clock): State<SharedClock>,
+    Query(params): Query<Params>,
+) -> Result<Json<Vec<Event>>, BadRequest> {
+    let at = params.at(&clock);
+    let clock = FixedClock::new(at);
+    Ok(Json(
+        params
+            .metas()?
+            .into_iter()
+            .flat_map(|kind| {
+                kind.info()
+                    .schedules
+                    .iter()
+                    .filter_map(|schedule| schedule.iter_from(&clock).now())
+                    .map(|event| Event::new(kind, &event, at.date_naive()))
+                    .collect::<Vec<Event>>()
+            })
+            .collect(),
+    ))
+}
+
+async fn upcoming(
+    State(clock): State<SharedClock>,
+    Query(params): Query<Params>,
+) -> Result<Json<Vec<Event>>, BadRequest> {
+    let limit = params.limit.unwrap_or(10);
+    let at = params.at(&clock);
+    let clock = FixedClock::new(at);
+    let mut events = params
+        .metas()?
+        .into_iter()
+        .flat_map(|kind| {
+            kind.iter_from(&clock)
+                .take(limit)
+                .map(|event| Event::new(kind, &event, at.date_naive()))
+                .collect::<Vec<Event>>()
+        })
+        .collect::<Vec<Event>>();
+    events.sort_by_key(|event| event.start);
+    events.truncate(limit);
+    Ok(Json(events))
+}
+
+async fn timetable(
+    State(clock): State<SharedClock>,
+    Query(params): Query<Params>,
+) -> Result<Json<Vec<Event>>, BadRequest> {
+    let date = params
+        .date
+        .unwrap_or_else(|| params.at(&clock).date_naive());
+    let day_start = date.and_time(NaiveTime::MIN).and_utc();
+
+    // Start a minute early so events starting right at midnight are included
+    let start = day_start - Duration::minutes(1);
+    let start_time = Duration::seconds(start.time().num_seconds_from_midnight() as i64);
+    let clock = FixedClock::new(start);
+    let mut events = params
+        .metas()?
+        .into_iter()
+        .flat_map(|kind| {
+            kind.iter_from(&clock)
+                .take_while(|event| event.start_time <= start_time + Duration::days(1))
+                .map(|event| Event::new(kind, &event, start.date_naive()))
+                .collect::<Vec<Event>>()
+        })
+        .collect::<Vec<Event>>();
+    events.sort_by_key(|event| event.start);
+    Ok(Json(events))
+}
@@ -0,0 +1,138 @@
+//! Async [Stream]s that yield [EventInstance]s as they happen
+//!
+//! Only available with the `tokio` feature enabled.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use chrono::Duration;
+use futures_core::Stream;
+use tokio::time::{sleep_until, Instant, Sleep};
+
+use crate::event::EventInstance;
+
+/// Something that happened to an [EventInstance]
+#[derive(Clone, Debug)]
+pub enum Notification {
+    /// The event has just started
+    Started(EventInstance),
+
+    /// The event has just ended
+    Ended(EventInstance),
+}
+
+/// A [Stream] that waits for each [EventInstance] from an iterator to start, then yields it
+///
+/// Create one by calling `into_stream()` on a [crate::cursor::Cursor]. Time is measured with
+/// tokio's clock, so the stream can be driven by tokio's paused time in tests.
+pub struct EventStream<I> {
+    events: I,
+
+    /// The time from UTC 00:00 the stream was started at
+    start_time: Duration,
+
+    /// The moment the stream was started
+    start_instant: Instant,
+
+    /// Whether to also yield [Notification::Ended]
+    endings: bool,
+
+    /// The next event to start
+    next_event: Option<EventInstance>,
+
+    /// Events that have started but not yet ended
+    active: Vec<EventInstance>,
+
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl<I: Iterator<Item = EventInstance>> EventStream<I> {
+    /// Create a stream from `events`, where `time` is the time from UTC 00:00 right now
+    pub fn new(events: I, time: Duration) -> Self {
+        EventStream {
+            events,
+            start_time: time,
+            start_instant: Instant::now(),
+            endings: false,
+            next_event: None,
+            active: Vec::new(),
+            sleep: None,
+        }
+    }
+
+    /// Also yield a [Notification::Ended] when each event ends
+    pub fn with_endings(mut self) -> Self {
+        self.endings = true;
+        self
+    }
+
+    /// Get the next notification due and the time from UTC 00:00 it's due at
+    fn next_due(&mut self) -> Option<(Duration, bool)> {
+        if self.next_event.is_none() {
+            self.next_event = self.events.next();
+        }
+
+        let start = self.next_event.as_ref().map(|event| event.start_time);
+        let end = self
+            .active
+            .iter()
+            .map(|event| event.start_time + event.schedule.length)
+            .min();
+
+        match (start, end) {
+            // An event ending at the same time another starts is ended first
+            (Some(start), Some(end)) if end <= start => Some((end, true)),
+            (Some(start), _) => Some((start, false)),
+            (None, Some(end)) => Some((end, true)),
+            (None, None) => None,
+        }
+    }
+
+    fn deadline(&self, time: Duration) -> Instant {
+        let until = (time - self.start_time)
+            .to_std()
+            .unwrap_or(std::time::Duration::ZERO);
+        self.start_instant + until
+    }
+}
+
+impl<I: Iterator<Item = EventInstance> + Unpin> Stream for EventStream<I> {
+    type Item = Notification;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Notification>> {
+        let (time, ending) = match self.next_due() {
+            Some(due) => due,
+            None => return Poll::Ready(None),
+        };
+
+        let deadline = self.deadline(time);
+        match &mut self.sleep {
+            Some(sleep) if sleep.deadline() != deadline => sleep.as_mut().reset(deadline),
+            Some(_) => {}
+            None => self.sleep = Some(Box::pin(sleep_until(deadline))),
+        }
+
+        if let Some(sleep) = &mut self.sleep {
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+
+        if ending {
+            let index = self
+                .active
+                .iter()
+                .position(|event| event.start_time + event.schedule.length == time);
+            return Poll::Ready(index.map(|index| Notification::Ended(self.active.remove(index))));
+        }
+
+        let event = self.next_event.take();
+        if let (true, Some(event)) = (self.endings, &event) {
+            self.active.push(event.clone());
+        }
+        Poll::Ready(event.map(Notification::Started))
+    }
+}
@@ -0,0 +1,369 @@
+//! Checks the [EventSchedule]s of a [MapMeta] for mistakes
+//!
+//! [validate] gives a [Diagnostic] for every problem found in a map meta. Some problems, like gaps
+//! between events or events overlapping each other, are only mistakes for certain metas, so what
+//! is expected of a meta is given with [Expectations].
+
+use core::fmt;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use chrono::{Duration, Timelike};
+
+use crate::{
+    cycle::CycleTable,
+    error::Error,
+    humanize::Humanizer,
+    meta::{MapMeta, MapMetaKind},
+    schedule::{whole_minutes, EventSchedule, MAX_LENGTH},
+};
+
+/// How serious a [Lint] is
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// The schedule works, but is probably not what was meant
+    Warning,
+
+    /// The schedule can't be iterated, or gives events twice
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+/// A problem found in a map meta
+///
+/// Schedules are given by their index in [MapMeta::schedules], and times are from UTC 00:00.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Lint {
+    /// The map meta has no schedules
+    NoSchedules,
+
+    /// The schedule's frequency is not a positive whole number of minutes
+    InvalidFrequency { schedule: usize },
+
+    /// The schedule's length is not a positive whole number of minutes
+    InvalidLength { schedule: usize },
+
+    /// The schedule's events last longer than four weeks
+    LengthTooLong { schedule: usize },
+
+    /// The schedule's offset is not on a whole minute
+    InvalidOffset { schedule: usize },
+
+    /// The schedule has the same name and times as an earlier one
+    Duplicate { schedule: usize, original: usize },
+
+    /// The schedule lasts longer than its frequency, so each event overlaps the next
+    LengthExceedsFrequency { schedule: usize },
+
+    /// The schedule's offset is later than its first repeat, e.g. 10:00 for every 2h
+    OffsetBeyondPeriod { schedule: usize },
+
+    /// The schedule's frequency doesn't divide a day, so it starts at different times each day
+    UnevenFrequency { schedule: usize },
+
+    /// Events of two schedules are active at the same time
+    Overlap { first: usize, second: usize },
+
+    /// No event is active between `start` and `end`
+    Gap { start: Duration, end: Duration },
+}
+
+impl Lint {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Lint::NoSchedules
+            | Lint::InvalidFrequency { .. }
+            | Lint::InvalidLength { .. }
+            | Lint::LengthTooLong { .. }
+            | Lint::InvalidOffset { .. }
+            | Lint::Duplicate { .. } => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+/// A [Lint] found in a map meta, with a message describing it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The name of the map meta
+    pub meta: &'static str,
+
+    pub lint: Lint,
+
+    /// A description of the problem, naming the schedules involved
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        self.lint.severity()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.meta, self.severity(), self.message)
+    }
+}
+
+/// What a map meta's schedules are meant to look like
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Expectations {
+    /// Whether an event should be active at every moment
+    pub continuous: bool,
+
+    /// Whether events may be active at the same time
+    pub overlapping: bool,
+}
+
+impl Expectations {
+    /// What is expected of a built-in map meta
+    pub fn of(kind: MapMetaKind) -> Self {
+        match kind {
+            MapMetaKind::DayAndNight
+            | MapMetaKind::WorldBosses
+            | MapMetaKind::DryTop
+            | MapMetaKind::VerdantBrink
+            | MapMetaKind::AuricBasin
+            | MapMetaKind::TangledDepths
+            | MapMetaKind::DragonsStand
+            | MapMetaKind::LakeDoric
+            | MapMetaKind::Cantha => Expectations {
+                continuous: true,
+                overlapping: false,
+            },
+            // Doppelganger is the last part of the Augury Rock chain
+            MapMetaKind::ElonRiverlands => Expectations {
+                continuous: false,
+                overlapping: true,
+            },
+            _ => Expectations::default(),
+        }
+    }
+}
+
+/// Check every schedule in `meta`, giving each problem found in the order of its schedules
+pub fn validate(meta: &MapMeta, expectations: Expectations) -> Vec<Diagnostic> {
+    let schedules = &meta.schedules;
+    let mut diagnostics = Vec::new();
+    let mut push = |lint: Lint, message: String| {
+        diagnostics.push(Diagnostic {
+            meta: meta.name,
+            lint,
+            message,
+        })
+    };
+
+    // Problems that stop the meta being built with MapMeta::new are described like its errors
+    if schedules.is_empty() {
+        push(Lint::NoSchedules, Error::NoSchedules.to_string());
+        return diagnostics;
+    }
+
+    let length = |duration: Duration| Humanizer::default().duration(duration);
+    let mut iterable = true;
+    for (index, schedule) in schedules.iter().enumerate() {
+        let name = schedule.name;
+        if !whole_minutes(schedule.frequency) {
+            push(
+                Lint::InvalidFrequency { schedule: index },
+                Error::InvalidFrequency {
+                    name,
+                    frequency: schedule.frequency,
+                }
+                .to_string(),
+            );
+        }
+        let frequency = schedule.frequency.num_minutes();
+        if frequency <= 0 {
+            iterable = false;
+            continue;
+        }
+        if !whole_minutes(schedule.length) {
+            push(
+                Lint::InvalidLength { schedule: index },
+                Error::InvalidLength {
+                    name,
+                    length: schedule.length,
+                }
+                .to_string(),
+            );
+        }
+        if schedule.length.num_minutes() > MAX_LENGTH {
+            push(
+                Lint::LengthTooLong { schedule: index },
+                Error::LengthTooLong {
+                    name,
+                    length: schedule.length,
+                }
+                .to_string(),
+            );
+        }
+        if schedule.offset.second() != 0 || schedule.offset.nanosecond() != 0 {
+            push(
+                Lint::InvalidOffset { schedule: index },
+                Error::InvalidOffset {
+                    name,
+                    offset: schedule.offset,
+                }
+                .to_string(),
+            );
+        }
+        if let Some(original) = schedules[..index].iter().position(|other| {
+            other.name == schedule.name
+                && other.offset == schedule.offset
+                && other.frequency == schedule.frequency
+                && other.length == schedule.length
+        }) {
+            push(
+                Lint::Duplicate {
+                    schedule: index,
+                    original,
+                },
+                format!("{} is listed more than once", schedule.name),
+            );
+        }
+        if schedule.length > schedule.frequency {
+            push(
+                Lint::LengthExceedsFrequency { schedule: index },
+                format!(
+                    "{} lasts {} but starts every {}",
+                    schedule.name,
+                    length(schedule.length),
+                    length(schedule.frequency)
+                ),
+            );
+        }
+        if offset(schedule) >= frequency {
+            push(
+                Lint::OffsetBeyondPeriod { schedule: index },
+                format!(
+                    "{} first starts at {} but starts every {}",
+                    schedule.name,
+                    schedule.offset.format("%H:%M"),
+                    length(schedule.frequency)
+                ),
+            );
+        }
+        if (24 * 60) % frequency != 0 {
+            push(
+                Lint::UnevenFrequency { schedule: index },
+                format!(
+                    "{} starts every {}, which doesn't divide a day",
+                    schedule.name,
+                    length(schedule.frequency)
+                ),
+            );
+        }
+    }
+    if !iterable {
+        return diagnostics;
+    }
+
+    if !expectations.overlapping {
+        for (first, a) in schedules.iter().enumerate() {
+            for (second, b) in schedules.iter().enumerate().skip(first + 1) {
+                if overlaps(a, b) {
+                    push(
+                        Lint::Overlap { first, second },
+                        format!("{} overlaps {}", a.name, b.name),
+                    );
+                }
+            }
+        }
+    }
+
+    if expectations.continuous {
+        for (start, end) in gaps(schedules) {
+            push(
+                Lint::Gap { start, end },
+                format!(
+                    "no event is active from {} to {}",
+                    clock_time(start),
+                    clock_time(end)
+                ),
+            );
+        }
+    }
+
+    diagnostics
+}
+
+/// Minutes after UTC 00:00 a schedule first starts
+fn offset(schedule: &EventSchedule) -> i64 {
+    schedule.offset.num_seconds_from_midnight() as i64 / 60
+}
+
+/// Whether any event of `a` is active at the same time as an event of `b`
+fn overlaps(a: &EventSchedule, b: &EventSchedule) -> bool {
+    // The starts of the two schedules can be any multiple of the gcd of their frequencies apart,
+    // plus the difference in their offsets
+    let step = gcd(a.frequency.num_minutes(), b.frequency.num_minutes());
+    let apart = (offset(b) - offset(a)).rem_euclid(step);
+    apart < a.length.num_minutes() || step - apart < b.length.num_minutes()
+}
+
+/// Every time within a period no event is active, from UTC 00:00
+///
+/// Schedules that repeat less often than every four weeks are only checked over the first four
+/// weeks.
+fn gaps(schedules: &[EventSchedule]) -> Vec<(Duration, Duration)> {
+    let table = CycleTable::new(schedules.to_vec());
+    let (period, repeats) = match table.period() {
+        Some(period) => (period, true),
+        None => (Duration::weeks(4), false),
+    };
+
+    // Start with the events before UTC 00:00, in case they last into the period
+    let start = -table.longest() - Duration::minutes(1);
+    let mut covered = start;
+    let mut gaps = Vec::new();
+    for index in table.index_after(start).. {
+        let event = match table.occurrence(index) {
+            Some(event) if event.start_time < period => event,
+            _ => break,
+        };
+        if event.start_time >= Duration::zero() && event.start_time > covered {
+            gaps.push((covered.max(Duration::zero()), event.start_time));
+        }
+        covered = covered.max(event.end_time());
+    }
+    if covered < period {
+        let covered = covered.max(Duration::zero());
+        match gaps
+            .first_mut()
+            .filter(|(start, _)| repeats && *start == Duration::zero())
+        {
+            // Joins the gap at the start of the period, which is the same gap
+            Some((start, _)) => *start = covered - period,
+            None => gaps.push((covered, period)),
+        }
+    }
+    gaps
+}
+
+/// A time of day for a time from UTC 00:00
+fn clock_time(time: Duration) -> String {
+    let minutes = time.num_minutes().rem_euclid(24 * 60);
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,188 @@
+//! JavaScript bindings for running in a browser or Node.js
+//!
+//! Only available with the `wasm` feature enabled. Build the npm package in `pkg` with:
+//!
+//! ```sh
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target bundler --out-dir pkg target/wasm32-unknown-unknown/release/gw2timers.wasm
+//! ```
+//!
+//! Every function takes an optional `Date` to look at another time than now, and returns plain
+//! objects with `Date` values for event times:
+//!
+//! ```js
+//! import { metas, active, upcoming } from "gw2timers";
+//!
+//! for (const event of upcoming(new Date(), 5, ["WorldBosses"])) {
+//!     console.log(`${event.name} starts at ${event.start.toLocaleTimeString()}`);
+//! }
+//! ```
+
+use chrono::{DateTime, NaiveDate, Utc};
+use js_sys::{Array, Date, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    clock::{Clock, FixedClock, SystemClock},
+    event::EventInstance,
+    meta::MapMetaKind,
+    schedule::EventSchedule,
+};
+
+#[wasm_bindgen(typescript_custom_section)]
+const TYPES: &'static str = r#"
+export interface Schedule {
+    name: string;
+    offset: string;
+    frequencyMinutes: number;
+    lengthMinutes: number;
+}
+
+export interface Meta {
+    id: string;
+    name: string;
+    category: string;
+    schedules: Schedule[];
+}
+
+export interface Event {
+    meta: string;
+    category: string;
+    name: string;
+    start: Date;
+    end: Date;
+}
+"#;
+
+/// Every map meta, with its id, name, category and event schedules
+#[wasm_bindgen(unchecked_return_type = "Meta[]")]
+pub fn metas() -> Array {
+    MapMetaKind::all_keys()
+        .iter()
+        .map(|kind| {
+            let meta = kind.info();
+            let schedules = meta
+                .schedules
+                .iter()
+                .map(schedule_object)
+                .collect::<Array>();
+            object(&[
+                ("id", format!("{:?}", kind).into()),
+                ("name", meta.name.into()),
+                ("category", meta.category.name().into()),
+                ("schedules", schedules.into()),
+            ])
+        })
+        .collect()
+}
+
+/// The events active at `at`, or now if it isn't given
+///
+/// `metas` limits the events to some map metas, by id or name.
+#[wasm_bindgen(unchecked_return_type = "Event[]")]
+pub fn active(at: Option<Date>, metas: Option<Vec<String>>) -> Result<Array, JsError> {
+    let at = datetime(at)?;
+    let clock = FixedClock::new(at);
+    Ok(parse_metas(metas)?
+        .into_iter()
+        .flat_map(|kind| {
+            kind.info()
+                .schedules
+                .iter()
+                .filter_map(|schedule| schedule.iter_from(&clock).now())
+                .map(|event| event_object(kind, &event, at.date_naive()))
+                .collect::<Vec<JsValue>>()
+        })
+        .collect())
+}
+
+/// The next `limit` events, 10 if it isn't given, to start after `at`, or now if it isn't given
+///
+/// `metas` limits the events to some map metas, by id or name.
+#[wasm_bindgen(unchecked_return_type = "Event[]")]
+pub fn upcoming(
+    at: Option<Date>,
+    limit: Option<usize>,
+    metas: Option<Vec<String>>,
+) -> Result<Array, JsError> {
+    let limit = limit.unwrap_or(10);
+    let at = datetime(at)?;
+    let clock = FixedClock::new(at);
+    let mut events = parse_metas(metas)?
+        .into_iter()
+        .flat_map(|kind| {
+            kind.iter_from(&clock)
+                .take(limit)
+                .map(|event| (kind, event))
+                .collect::<Vec<(MapMetaKind, EventInstance)>>()
+        })
+        .collect::<Vec<(MapMetaKind, EventInstance)>>();
+    events.sort_by_key(|(_, event)| event.start_time);
+    Ok(events
+        .iter()
+        .take(limit)
+        .map(|(kind, event)| event_object(*kind, event, at.date_naive()))
+        .collect())
+}
+
+fn datetime(at: Option<Date>) -> Result<DateTime<Utc>, JsError> {
+    match at {
+        // An Invalid Date has a time of NaN
+        Some(at) => Some(at.get_time())
+            .filter(|time| time.is_finite())
+            .and_then(|time| DateTime::from_timestamp_millis(time as i64))
+            .ok_or_else(|| JsError::new("invalid date")),
+        None => Ok(SystemClock.now()),
+    }
+}
+
+fn parse_metas(metas: Option<Vec<String>>) -> Result<Vec<MapMetaKind>, JsError> {
+    match metas {
+        Some(metas) => metas
+            .iter()
+            .map(|meta| {
+                meta.parse::<MapMetaKind>()
+                    .map_err(|e| JsError::new(&format!("{}: '{}'", e, meta)))
+            })
+            .collect(),
+        None => Ok(MapMetaKind::all_keys().to_vec()),
+    }
+}
+
+fn js_date(time: DateTime<Utc>) -> Date {
+    Date::new(&JsValue::from_f64(time.timestamp_millis() as f64))
+}
+
+fn object(properties: &[(&str, JsValue)]) -> JsValue {
+    let object = Object::new();
+    for (key, value) in properties {
+        Reflect::set(&object, &JsValue::from_str(key), value).unwrap();
+    }
+    object.into()
+}
+
+fn schedule_object(schedule: &EventSchedule) -> JsValue {
+    object(&[
+        ("name", schedule.name.into()),
+        ("offset", schedule.offset.format("%H:%M").to_string().into()),
+        (
+            "frequencyMinutes",
+            (schedule.frequency.num_minutes() as f64).into(),
+        ),
+        (
+            "lengthMinutes",
+            (schedule.length.num_minutes() as f64).into(),
+        ),
+    ])
+}
+
+fn event_object(kind: MapMetaKind, event: &EventInstance, date: NaiveDate) -> JsValue {
+    let meta = kind.info();
+    object(&[
+        ("meta", meta.name.into()),
+        ("category", meta.category.name().into()),
+        ("name", event.schedule.name.into()),
+        ("start", js_date(event.start_datetime(date)).into()),
+        ("end", js_date(event.end_datetime(date)).into()),
+    ])
+}